@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use gol_atomic::*;
+
+const H: usize = 500;
+const W: usize = 500;
+
+// Copy the state of a random 500x500 grid serially and split between rayon threads
+fn copy(c: &mut Criterion) {
+    let other = Grid::<H, W>::new();
+    randomize_grid_seeded(&other, 3, 0.5);
+    let grid = Grid::<H, W>::new();
+
+    let mut group = c.benchmark_group("copy");
+    group.bench_function("copy_from", |b| b.iter(|| grid.copy_from(&other)));
+    group.bench_function("copy_from_parallel", |b| {
+        b.iter(|| grid.copy_from_parallel(&other))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, copy);
+criterion_main!(benches);
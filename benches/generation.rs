@@ -0,0 +1,59 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use gol_atomic::{checked::validate_generation, *};
+
+const H: usize = 200;
+const W: usize = 200;
+
+// Generate a random 200x200 grid with every generator after checking it on a pulsar
+fn generation(c: &mut Criterion) {
+    validate_generation("generate", |grid| Generator::new(grid).generate());
+    validate_generation("generate_parallel", |grid| {
+        Generator::new(grid).generate_parallel(4)
+    });
+    validate_generation("compact", |grid| CompactGenerator::new(grid).generate());
+
+    let grid = Grid::<H, W>::new();
+    randomize_grid_seeded(&grid, 1842, 0.5);
+
+    let mut group = c.benchmark_group("generation");
+
+    let generator = Generator::new(&grid);
+    group.bench_function("generate", |b| b.iter(|| generator.generate()));
+    group.bench_function("generate_parallel", |b| {
+        b.iter(|| generator.generate_parallel(4))
+    });
+
+    let compact = CompactGenerator::new(&grid);
+    group.bench_function("compact", |b| b.iter(|| compact.generate()));
+
+    group.finish();
+}
+
+// Compare the column by column scan with the row by row scan on a tall grid
+fn column_major(c: &mut Criterion) {
+    let grid = Grid::<1000, 100>::new();
+    randomize_grid_seeded(&grid, 1862, 0.4);
+    let generator = Generator::new(&grid);
+
+    let mut group = c.benchmark_group("column_major");
+    group.bench_function("row_major", |b| b.iter(|| generator.generate()));
+    group.bench_function("column_major", |b| {
+        b.iter(|| generator.generate_column_major())
+    });
+    group.finish();
+}
+
+// Compare the Z-order scan with the row by row scan on a square power of two grid
+fn morton(c: &mut Criterion) {
+    let grid = Grid::<256, 256>::new();
+    randomize_grid_seeded(&grid, 1894, 0.4);
+    let generator = Generator::new(&grid);
+
+    let mut group = c.benchmark_group("morton");
+    group.bench_function("row_major", |b| b.iter(|| generator.generate()));
+    group.bench_function("morton", |b| b.iter(|| generator.generate_morton()));
+    group.finish();
+}
+
+criterion_group!(benches, generation, column_major, morton);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use gol_atomic::*;
+
+const H: usize = 64;
+const W: usize = 64;
+
+// Spawn a 16x8 rectangle of 128 cells into an empty grid,
+// once with spawn_batch and once cell by cell with spawn
+// The grids are returned so dropping them is not measured
+fn spawn(c: &mut Criterion) {
+    let pattern: Vec<(isize, isize)> = (0..8)
+        .flat_map(|y| (0..16).map(move |x| (x + 8, y + 8)))
+        .collect();
+
+    let mut group = c.benchmark_group("spawn");
+
+    group.bench_function("spawn_batch", |b| {
+        b.iter_batched(
+            Grid::<H, W>::new,
+            |grid| {
+                grid.spawn_batch(&pattern);
+                grid
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("spawn", |b| {
+        b.iter_batched(
+            Grid::<H, W>::new,
+            |grid| {
+                for (x, y) in &pattern {
+                    grid.spawn(*x, *y);
+                }
+                grid
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, spawn);
+criterion_main!(benches);
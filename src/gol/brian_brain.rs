@@ -0,0 +1,109 @@
+use crate::gol::{generator::Generate, grid::Grid, state_grid::StateGrid};
+
+// Brian's Brain: a three-state automaton where cells go alive -> dying -> dead
+// Dead cells with exactly 2 alive neighbors are born. Dying cells use the
+// dying bit of the cell and do not count as neighbors
+pub struct BrianBrainGenerator<'a, const H: usize, const W: usize> {
+    grid: &'a Grid<H, W>,
+    cache: StateGrid<H, W>,
+}
+
+impl<'a, const H: usize, const W: usize> BrianBrainGenerator<'a, H, W> {
+    pub fn new(grid: &'a Grid<H, W>) -> Self {
+        Self {
+            grid,
+            cache: StateGrid::new(),
+        }
+    }
+
+    pub fn generate(&self) {
+        self.cache.copy_from(self.grid);
+
+        for y in 0..H {
+            for x in 0..W {
+                let x = x as isize;
+                let y = y as isize;
+
+                let cell = self.cache.get(x, y);
+
+                if *cell == 0b00000000 {
+                    continue;
+                }
+
+                if cell.alive() {
+                    self.grid.kill(x, y);
+                    self.grid.get(x, y).set_dying();
+                } else if cell.is_dying() {
+                    self.grid.get(x, y).clear_dying();
+                } else if cell.neighbors() == 2 {
+                    self.grid.spawn(x, y);
+                }
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+// Implement Generate for BrianBrainGenerator
+impl<'a, const H: usize, const W: usize> Generate<H, W> for BrianBrainGenerator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    #[test]
+    fn test_ignition() {
+        let grid = Grid::<8, 8>::new();
+
+        /* Two alive cells ignite the four cells above and below them
+           [0][0][0][0]      [0][1][1][0]
+           [0][1][1][0]  ->  [0][d][d][0]
+           [0][0][0][0]      [0][1][1][0]
+        */
+        grid.spawn_shape((3, 3), &[(0, 0), (1, 0)]);
+
+        let generator = BrianBrainGenerator::<8, 8>::new(&grid);
+        generator.generate();
+
+        for (x, y) in [(3, 3), (4, 3)] {
+            let cell = grid.get(x, y);
+            assert!(!cell.alive());
+            assert!(cell.is_dying());
+        }
+
+        for (x, y) in [(3, 2), (4, 2), (3, 4), (4, 4)] {
+            let cell = grid.get(x, y);
+            assert!(cell.alive());
+            assert!(!cell.is_dying());
+        }
+
+        assert_eq!(grid.population(), 4);
+        assert!(grid.verify_neighbors());
+
+        // Dying cells die, the new cells start dying
+        generator.generate();
+
+        for (x, y) in [(3, 3), (4, 3)] {
+            let cell = grid.get(x, y);
+            assert!(!cell.alive());
+            assert!(!cell.is_dying());
+        }
+
+        for (x, y) in [(3, 2), (4, 2), (3, 4), (4, 4)] {
+            assert!(grid.get(x, y).is_dying());
+        }
+
+        assert!(grid.verify_neighbors());
+    }
+}
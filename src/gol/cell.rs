@@ -30,10 +30,14 @@ impl Cell {
 
     #[inline]
     // Bitwise atomic operation to set the first bit to 1
-    pub fn spawn(&self) {
-        self.state
+    // Returns true if the cell was dead before
+    pub fn spawn(&self) -> bool {
+        let old = self
+            .state
             .fetch_update(self.store, self.fetch, |old| Some(old | 1))
             .unwrap();
+
+        old & 1 == 0
     }
 
     #[inline]
@@ -57,7 +61,7 @@ impl Cell {
             .fetch_update(self.store, self.fetch, |mut old| {
                 let count = (old >> 1) & 0b1111;
                 if count + 1 <= 8 {
-                    old = (old & 0b0000_0001) | ((count + 1) << 1);
+                    old = (old & !0b0001_1110) | ((count + 1) << 1);
                     Some(old)
                 } else {
                     None
@@ -69,6 +73,26 @@ impl Cell {
             ));
     }
 
+    #[inline]
+    // Bitwise atomic operation to increment the number of neighbors by count
+    pub fn add_neighbors(&self, count: u8) {
+        self.state
+            .fetch_update(self.store, self.fetch, |mut old| {
+                let current = (old >> 1) & 0b1111;
+                if current + count <= 8 {
+                    old = (old & !0b0001_1110) | ((current + count) << 1);
+                    Some(old)
+                } else {
+                    None
+                }
+            })
+            .expect(&format!(
+                "Add: Neighbor count must be between 0 and 8, is currently {} + {}",
+                self.neighbors(),
+                count
+            ));
+    }
+
     #[inline]
     // Bitwise atomic operation to decrement the number of neighbors
     pub fn remove_neighbor(&self) {
@@ -76,7 +100,7 @@ impl Cell {
             .fetch_update(self.store, self.fetch, |mut old| {
                 let count = (old >> 1) & 0b1111;
                 if count > 0 {
-                    old = (old & 0b0000_0001) | ((count - 1) << 1);
+                    old = (old & !0b0001_1110) | ((count - 1) << 1);
                     Some(old)
                 } else {
                     None
@@ -94,18 +118,90 @@ impl Cell {
         self.state.load(self.fetch) & 1 == 1
     }
 
+    #[inline]
+    // Bitwise atomic operation to get the age stored in the upper 3 bits
+    // Used by automata where cells decay over several generations
+    pub fn age(&self) -> u8 {
+        self.state.load(self.fetch) >> 5
+    }
+
+    #[inline]
+    // Bitwise atomic operation to set the age stored in the upper 3 bits
+    pub fn set_age(&self, age: u8) {
+        assert!(age <= 0b111, "Age must be between 0 and 7, is {}", age);
+        self.state
+            .fetch_update(self.store, self.fetch, |old| {
+                Some((old & 0b0001_1111) | (age << 5))
+            })
+            .unwrap();
+    }
+
+    #[inline]
+    // Bitwise atomic operation to set the dying bit (bit 5, age 1) to 1
+    // Used by three-state automata like Brian's Brain
+    pub fn set_dying(&self) {
+        self.set_age(1);
+    }
+
+    #[inline]
+    // Bitwise atomic operation to reset the age, clearing the dying bit
+    pub fn clear_dying(&self) {
+        self.set_age(0);
+    }
+
+    #[inline]
+    // Bitwise atomic operation, returns true if the cell has a non-zero age
+    pub fn is_dying(&self) -> bool {
+        self.age() != 0
+    }
+
     #[inline]
     // Atomically loads the value of the cell with the specified ordering
     pub fn fetch(&self) -> u8 {
         self.state.load(self.fetch)
     }
 
+    #[inline]
+    // Load and store orderings the cell was created with
+    pub fn orderings(&self) -> (Ordering, Ordering) {
+        (self.fetch, self.store)
+    }
+
     #[inline]
     // Atomically stores the value of the cell with the specified ordering
+    // Unchecked: the neighbor counts of the surrounding cells are not updated,
+    // use Grid::set_cell_consistent to change the alive state of a cell in a grid
     pub fn store(&self, value: u8) {
         self.state.store(value, self.store);
     }
 
+    #[inline]
+    // Atomically replace the value of the cell, returning the previous value
+    // The swap loads with the fetch ordering and stores with the store ordering
+    pub fn exchange(&self, value: u8) -> u8 {
+        self.state.swap(value, self.swap_ordering())
+    }
+
+    #[inline]
+    // Single read-modify-write ordering combining the fetch and store orderings
+    fn swap_ordering(&self) -> Ordering {
+        match (self.fetch, self.store) {
+            (SeqCst, _) | (_, SeqCst) => SeqCst,
+            (Acquire, Release) => AcqRel,
+            (Acquire, _) => Acquire,
+            (_, Release) => Release,
+            _ => Ordering::Relaxed,
+        }
+    }
+
+    #[inline]
+    // Copy the value of another cell with relaxed ordering
+    // Only safe to rely on once the copying threads have been joined
+    pub fn copy_relaxed(&self, other: &Cell) {
+        self.state
+            .store(other.state.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
     #[inline]
     // Atomically swap the value of the cell with another cell
     pub fn compare_and_swap(&self, other: &Cell) {
@@ -244,6 +340,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dying() {
+        let cell = Cell::default();
+        cell.set_dying();
+        assert!(cell.is_dying());
+        assert!(!cell.alive());
+
+        // Neighbor bookkeeping leaves the dying bit untouched
+        cell.add_neighbor();
+        cell.add_neighbors(2);
+        cell.remove_neighbor();
+        assert!(cell.is_dying());
+        assert_eq!(cell.neighbors(), 2);
+        assert_eq!(cell.fetch(), 0b0010_0100);
+
+        cell.clear_dying();
+        assert!(!cell.is_dying());
+        assert_eq!(cell.fetch(), 0b0000_0100);
+    }
+
+    #[test]
+    fn test_age() {
+        let cell = Cell::default();
+        cell.spawn();
+        cell.add_neighbors(8);
+
+        for age in 0..=7 {
+            cell.set_age(age);
+            assert_eq!(cell.age(), age);
+            assert_eq!(cell.is_dying(), age != 0);
+            assert_eq!(cell.neighbors(), 8);
+            assert!(cell.alive());
+        }
+    }
+
+    #[test]
+    fn test_add_neighbors() {
+        let cell = Cell::default();
+        cell.spawn();
+
+        cell.add_neighbors(3);
+        assert_eq!(cell.neighbors(), 3);
+        assert!(cell.alive());
+
+        cell.add_neighbors(5);
+        assert_eq!(cell.fetch(), 0b0001_0001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_neighbors_overflow() {
+        let cell = Cell::default();
+        cell.add_neighbors(6);
+        cell.add_neighbors(3);
+    }
+
+    #[test]
+    fn test_exchange() {
+        let cell = Cell::default();
+        cell.store(0b0000_0111);
+
+        assert_eq!(cell.exchange(0b0000_0001), 0b0000_0111);
+        assert_eq!(cell.fetch(), 0b0000_0001);
+
+        // The swap uses the orderings the cell was created with
+        assert_eq!(cell.swap_ordering(), AcqRel);
+        assert_eq!(
+            Cell::new(Ordering::Relaxed, Ordering::Relaxed).swap_ordering(),
+            Ordering::Relaxed
+        );
+        assert_eq!(
+            Cell::new(Acquire, Ordering::Relaxed).swap_ordering(),
+            Acquire
+        );
+        assert_eq!(
+            Cell::new(Ordering::Relaxed, Release).swap_ordering(),
+            Release
+        );
+        assert_eq!(Cell::new(SeqCst, Release).swap_ordering(), SeqCst);
+    }
+
     #[test]
     fn test_data_race() {
         use std::thread;
@@ -253,6 +430,7 @@ mod tests {
 
         unsafe impl Sync for Wrapper {}
 
+        #[allow(clippy::mut_from_ref)]
         fn get_unsafe_value(value: &Arc<Wrapper>) -> &mut i32 {
             unsafe { &mut *value.0.get() }
         }
@@ -0,0 +1,239 @@
+use crate::gol::{
+    generator::{Generate, Generator},
+    grid::Grid,
+};
+
+// Debug decorator that recounts every neighbor after each step
+// Panics with the coordinates of the first corrupted cell
+pub struct CheckedGenerator<G> {
+    inner: G,
+}
+
+// Implement CheckedGenerator
+impl<G> CheckedGenerator<G> {
+    pub fn new(inner: G) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+// Implement Generate for CheckedGenerator
+impl<G: Generate<H, W>, const H: usize, const W: usize> Generate<H, W> for CheckedGenerator<G> {
+    fn step(&self) {
+        self.inner.step();
+
+        if let Some((x, y)) = self.inner.grid().neighbor_mismatch() {
+            panic!(
+                "Neighbor count of cell ({}, {}) is {}, expected {}",
+                x,
+                y,
+                self.inner.grid().get(x, y).neighbors(),
+                self.inner.grid().live_neighbor_mask(x, y).count_ones()
+            );
+        }
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        self.inner.grid()
+    }
+}
+
+// Gosper glider gun emitting a glider towards the bottom right every 30 generations
+#[rustfmt::skip]
+pub const GOSPER_GUN_OFFSETS: [(isize, isize); 36] = [
+    (0, 4), (0, 5), (1, 4), (1, 5),
+    (10, 4), (10, 5), (10, 6), (11, 3), (11, 7), (12, 2), (12, 8), (13, 2), (13, 8),
+    (14, 5), (15, 3), (15, 7), (16, 4), (16, 5), (16, 6), (17, 5),
+    (20, 2), (20, 3), (20, 4), (21, 2), (21, 3), (21, 4), (22, 1), (22, 5),
+    (24, 0), (24, 1), (24, 5), (24, 6),
+    (34, 2), (34, 3), (35, 2), (35, 3),
+];
+
+// One quadrant of a pulsar, mirrored into the other three
+#[rustfmt::skip]
+const PULSAR_QUADRANT: [(isize, isize); 12] = [
+    (2, 0), (3, 0), (4, 0),
+    (0, 2), (0, 3), (0, 4),
+    (5, 2), (5, 3), (5, 4),
+    (2, 5), (3, 5), (4, 5),
+];
+
+// Population of the three pulsar phases, starting after the first generation
+const PULSAR_POPULATIONS: [usize; 3] = [56, 72, 48];
+
+pub fn pulsar_offsets() -> Vec<(isize, isize)> {
+    PULSAR_QUADRANT
+        .iter()
+        .flat_map(|(x, y)| [(*x, *y), (12 - x, *y), (*x, 12 - y), (12 - x, 12 - y)])
+        .collect()
+}
+
+// Run a generation function on a pulsar for two periods and check every phase
+// The benchmarks call this first so a fast but broken generation cannot pass
+pub fn validate_generation(name: &str, generate: impl Fn(&Grid<17, 17>)) {
+    let grid = Grid::<17, 17>::new();
+    grid.spawn_shape((2, 2), &pulsar_offsets());
+    let initial = grid.to_bytes();
+
+    for generation in 0..6 {
+        generate(&grid);
+
+        assert_eq!(
+            grid.population(),
+            PULSAR_POPULATIONS[generation % 3],
+            "{}: wrong population after generation {}",
+            name,
+            generation + 1
+        );
+        assert!(
+            grid.verify_neighbors(),
+            "{}: corrupted neighbor counts after generation {}",
+            name,
+            generation + 1
+        );
+    }
+
+    assert_eq!(
+        grid.to_bytes(),
+        initial,
+        "{}: pulsar did not oscillate",
+        name
+    );
+}
+
+// Spawn the initial cells on an empty H x W grid, generate `after` generations
+// and assert that exactly the expected cells are alive, in any order
+#[track_caller]
+pub fn assert_becomes<const H: usize, const W: usize>(
+    initial: &[(isize, isize)],
+    after: usize,
+    expected: &[(isize, isize)],
+) {
+    let grid = Grid::<H, W>::new();
+    grid.spawn_batch(initial);
+
+    let generator = Generator::new(&grid);
+    for _ in 0..after {
+        generator.generate();
+    }
+
+    let mut alive: Vec<(isize, isize)> = grid
+        .enumerate_cells()
+        .filter(|(_, cell)| cell.alive())
+        .map(|(coords, _)| coords)
+        .collect();
+    let mut expected = expected.to_vec();
+
+    alive.sort();
+    expected.sort();
+    expected.dedup();
+
+    assert_eq!(
+        alive, expected,
+        "Wrong cells alive after {} generations",
+        after
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gol::*;
+
+    #[test]
+    fn test_checked_pulsar() {
+        let grid = Grid::<17, 17>::new();
+        grid.spawn_shape((2, 2), &pulsar_offsets());
+        assert_eq!(grid.population(), 48);
+
+        let initial = grid.to_bytes();
+        let generator = CheckedGenerator::new(Generator::new(&grid));
+
+        for _ in 0..9 {
+            generator.step();
+        }
+
+        // The pulsar oscillates with period 3
+        assert_eq!(generator.grid().to_bytes(), initial);
+        generator.step();
+    }
+
+    #[test]
+    #[should_panic(expected = "Neighbor count of cell")]
+    fn test_checked_corruption() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        grid.get(6, 6).add_neighbor();
+
+        let generator = CheckedGenerator::new(Generator::new(&grid));
+        generator.step();
+    }
+
+    #[test]
+    fn test_assert_becomes() {
+        let blinker = [(1, 2), (2, 2), (3, 2)];
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        assert_becomes::<5, 5>(&blinker, 0, &blinker);
+        assert_becomes::<5, 5>(&blinker, 1, &[(2, 1), (2, 2), (2, 3)]);
+        assert_becomes::<5, 5>(&blinker, 2, &blinker);
+
+        // One period moves the glider one cell down and right
+        let moved: Vec<(isize, isize)> = glider.iter().map(|(x, y)| (x + 1, y + 1)).collect();
+        assert_becomes::<8, 8>(&glider, 4, &moved);
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong cells alive after 1 generations")]
+    fn test_assert_becomes_mismatch() {
+        let blinker = [(1, 2), (2, 2), (3, 2)];
+        assert_becomes::<5, 5>(&blinker, 1, &blinker);
+    }
+
+    #[test]
+    fn test_gosper_gun_population() {
+        let grid = Grid::<64, 64>::new();
+        grid.spawn_shape((2, 2), &GOSPER_GUN_OFFSETS);
+
+        // Two gliders of five cells each have left the gun after 60 generations
+        let mut generator = Generator::new(&grid);
+        assert_eq!(generator.run_collecting_population(60)[59], 46);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    #[ignore = "Runs for several seconds in debug builds"]
+    fn test_gosper_gun_stress() {
+        const H: usize = 512;
+        const W: usize = 512;
+        const GENERATIONS: usize = 1000;
+
+        // The torus is large enough that no glider wraps around into the gun
+        let grid = Grid::<H, W>::new();
+        grid.spawn_shape((10, 10), &GOSPER_GUN_OFFSETS);
+
+        let mut generator = Generator::new(&grid);
+        let peak = generator.run_peak_population(GENERATIONS);
+
+        assert_eq!(peak, 226);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_validate_generation() {
+        validate_generation("generate", |grid| Generator::new(grid).generate());
+        validate_generation("generate_parallel", |grid| {
+            Generator::new(grid).generate_parallel(4)
+        });
+        validate_generation("compact", |grid| CompactGenerator::new(grid).generate());
+    }
+
+    #[test]
+    #[should_panic(expected = "skip: wrong population after generation 1")]
+    fn test_validate_generation_rejects_noop() {
+        validate_generation("skip", |_| {});
+    }
+}
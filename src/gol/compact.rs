@@ -0,0 +1,88 @@
+use crate::gol::{generator::Generate, grid::Grid, rule::Rule};
+
+use std::cell::RefCell;
+
+// Generator that keeps the previous generation as plain bytes instead of a cache grid
+// The snapshot holds the same alive bit and neighbor count layout as the cells
+pub struct CompactGenerator<'a, const H: usize, const W: usize> {
+    grid: &'a Grid<H, W>,
+    snapshot: RefCell<Vec<u8>>,
+    rule: Rule,
+}
+
+impl<'a, const H: usize, const W: usize> CompactGenerator<'a, H, W> {
+    pub fn new(grid: &'a Grid<H, W>) -> Self {
+        Self::with_rule(grid, Rule::conway())
+    }
+
+    // Create a generator applying the given birth/survival rule
+    pub fn with_rule(grid: &'a Grid<H, W>, rule: Rule) -> Self {
+        Self {
+            grid,
+            snapshot: RefCell::new(vec![0; H * W]),
+            rule,
+        }
+    }
+
+    pub fn generate(&self) {
+        let mut snapshot = self.snapshot.borrow_mut();
+        self.grid.as_bytes_into(&mut snapshot);
+
+        let skip_empty = !self.rule.born(0);
+
+        for (i, state) in snapshot.iter().enumerate() {
+            if skip_empty && *state == 0b00000000 {
+                continue;
+            }
+
+            let x = (i % W) as isize;
+            let y = (i / W) as isize;
+            let neighbor_count = (state >> 1) & 0b1111;
+
+            if state & 1 == 1 {
+                if !self.rule.survives(neighbor_count) {
+                    self.grid.kill(x, y);
+                }
+            } else if self.rule.born(neighbor_count) {
+                self.grid.spawn(x, y);
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+// Implement Generate for CompactGenerator
+impl<'a, const H: usize, const W: usize> Generate<H, W> for CompactGenerator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    #[test]
+    fn test_matches_generator() {
+        let grid = Grid::<20, 24>::new();
+        randomize_grid_seeded(&grid, 1839, 0.4);
+
+        let reference = grid.clone();
+
+        let generator = CompactGenerator::<20, 24>::new(&grid);
+        let reference_generator = Generator::<20, 24>::new(&reference);
+
+        for _ in 0..10 {
+            generator.generate();
+            reference_generator.generate();
+            assert_eq!(grid.to_bytes(), reference.to_bytes());
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::ops::{Add, Sub};
+
+// Named 2D coordinates of a cell: x is the column and y the row
+// Can be used instead of (x, y) tuples wherever the order is easy to mix up
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Coord {
+    pub x: isize,
+    pub y: isize,
+}
+
+// Implement Coord
+impl Coord {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    // The same cell on a w x h torus with both coordinates inside the grid
+    pub fn wrapped(self, w: usize, h: usize) -> Self {
+        Self {
+            x: self.x.rem_euclid(w as isize),
+            y: self.y.rem_euclid(h as isize),
+        }
+    }
+}
+
+// Implement Add for Coord
+impl Add for Coord {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+// Implement Sub for Coord
+impl Sub for Coord {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+// Implement From<(isize, isize)> for Coord
+impl From<(isize, isize)> for Coord {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+// Implement From<Coord> for (isize, isize)
+impl From<Coord> for (isize, isize) {
+    fn from(coord: Coord) -> Self {
+        (coord.x, coord.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    #[test]
+    fn test_coord() {
+        let coord = Coord::new(9, -1);
+        assert_eq!(coord.wrapped(9, 5), Coord::new(0, 4));
+        assert_eq!(coord + Coord::new(1, 2), Coord::new(10, 1));
+        assert_eq!(coord - Coord::new(1, 2), Coord::new(8, -3));
+        assert_eq!(Coord::from((3, 4)), Coord { x: 3, y: 4 });
+        assert_eq!(<(isize, isize)>::from(Coord::new(3, 4)), (3, 4));
+    }
+
+    #[test]
+    fn test_coord_grid() {
+        // 5 rows of 9 columns, so swapping x and y would land outside the rows
+        let grid = Grid::<5, 9>::new();
+        let coord = Coord::new(7, 2);
+
+        grid.spawn_at(coord);
+        assert!(grid.get(7, 2).alive());
+        assert!(!grid.get(2, 7).alive());
+        assert!(grid.get_at(coord).alive());
+
+        // Neighbors across the right edge wrap to the first column
+        grid.spawn_at(Coord::new(8, 2) + Coord::new(1, 0));
+        assert!(grid.get(0, 2).alive());
+        assert_eq!(grid.get_at(Coord::new(8, 2)).neighbors(), 2);
+
+        grid.kill_at(coord);
+        assert_eq!(grid.population(), 1);
+        assert!(grid.verify_neighbors());
+    }
+}
@@ -0,0 +1,404 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::gol::{
+    cell::Cell,
+    grid::{neighbor_indices, NEIGHBOR_OFFSETS},
+};
+
+// Largest number of cells a DynGrid may hold, e.g. 8192 x 8192
+// Caps allocations requested through the FFI with untrusted sizes
+pub const MAX_CELLS: usize = 1 << 26;
+
+// 2D interface to a vector of cells with dimensions known only at runtime
+// Mirrors Grid for callers that cannot use const generics (FFI, files)
+pub struct DynGrid {
+    w: usize,
+    h: usize,
+    cells: Vec<Cell>,
+}
+
+// Implement DynGrid
+impl DynGrid {
+    // Create a new grid with dead cells and 0 neighbors
+    pub fn new(w: usize, h: usize) -> Self {
+        assert!(w > 0 && h > 0, "Grid dimensions must be non-zero");
+        Self::try_new(w, h).expect("Grid must have at most MAX_CELLS cells")
+    }
+
+    // Create a new grid, or None if a dimension is zero or the grid
+    // would have more than MAX_CELLS cells
+    pub fn try_new(w: usize, h: usize) -> Option<Self> {
+        let len = w
+            .checked_mul(h)
+            .filter(|len| (1..=MAX_CELLS).contains(len))?;
+
+        let mut cells = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            cells.push(Cell::default());
+        }
+
+        Some(Self { w, h, cells })
+    }
+
+    #[inline]
+    // Width of the grid
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    #[inline]
+    // Height of the grid
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    #[inline]
+    // Index the grid with 2D coordinates
+    pub fn get(&self, x: isize, y: isize) -> &Cell {
+        let w = self.w as isize;
+        let h = self.h as isize;
+
+        let wrapped_x = ((x % w + w) % w) as usize;
+        let wrapped_y = ((y % h + h) % h) as usize;
+
+        &self.cells[wrapped_y * self.w + wrapped_x]
+    }
+
+    #[inline]
+    // Spawn a cell at the given 2D coordinates
+    // and increment the neighbors of its distinct surrounding cells
+    // Spawning a cell that is already alive does nothing
+    pub fn spawn(&self, x: isize, y: isize) {
+        let cell = self.get(x, y);
+        if !cell.spawn() {
+            return;
+        }
+
+        let (indices, len) = neighbor_indices(x, y, self.w, self.h);
+
+        for index in &indices[..len] {
+            self.cells[*index].add_neighbor();
+        }
+    }
+
+    #[inline]
+    // Kill a cell at the given 2D coordinates
+    // and decrement the neighbors of its distinct surrounding cells
+    pub fn kill(&self, x: isize, y: isize) {
+        let cell = self.get(x, y);
+        let (indices, len) = neighbor_indices(x, y, self.w, self.h);
+        cell.kill();
+
+        for index in &indices[..len] {
+            self.cells[*index].remove_neighbor();
+        }
+    }
+
+    #[inline]
+    // Copy the state byte of every cell of a grid of the same size
+    // The cells keep their own load and store orderings
+    pub fn copy_from(&self, other: &Self) {
+        assert_eq!(
+            (self.w, self.h),
+            (other.w, other.h),
+            "Grids must have the same size"
+        );
+
+        for (cell, other) in self.cells.iter().zip(other.cells.iter()) {
+            cell.store(other.fetch());
+        }
+    }
+
+    // Utility function to get the wrapped 2D coordinates
+    #[inline]
+    pub fn neighbor_coordinates(&self, x: isize, y: isize) -> [(isize, isize); 8] {
+        NEIGHBOR_OFFSETS.map(|(dx, dy)| (x.wrapping_add(dx), y.wrapping_add(dy)))
+    }
+}
+
+// Object safe generator interface independent of the grid size
+// Lets a UI hold a Box<dyn DynGenerator> and switch algorithms at runtime
+pub trait DynGenerator {
+    // Advance the grid by one generation
+    fn step(&self);
+
+    // Width of the grid
+    fn width(&self) -> usize;
+
+    // Height of the grid
+    fn height(&self) -> usize;
+
+    // Raw state byte of the cell at the given 2D coordinates
+    fn cell(&self, x: isize, y: isize) -> u8;
+}
+
+// Owns a runtime sized grid and its cache and advances it one generation at a time
+pub struct DynGridGenerator {
+    grid: DynGrid,
+    cache: DynGrid,
+    generation: AtomicUsize,
+}
+
+// Implement DynGridGenerator
+impl DynGridGenerator {
+    pub fn new(grid: DynGrid) -> Self {
+        let cache = DynGrid::new(grid.width(), grid.height());
+        Self {
+            grid,
+            cache,
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn generate(&self) {
+        self.cache.copy_from(&self.grid);
+
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let x = x as isize;
+                let y = y as isize;
+
+                let cell = self.cache.get(x, y);
+
+                if *cell == 0b00000000 {
+                    continue;
+                }
+
+                let neighbor_count = cell.neighbors();
+
+                if cell.alive() {
+                    if neighbor_count < 2 || neighbor_count > 3 {
+                        self.grid.kill(x, y);
+                    }
+                } else if neighbor_count == 3 {
+                    self.grid.spawn(x, y);
+                }
+            }
+        }
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Number of generations generated so far
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    pub fn grid(&self) -> &DynGrid {
+        &self.grid
+    }
+}
+
+// Implement DynGenerator for DynGridGenerator
+impl DynGenerator for DynGridGenerator {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn cell(&self, x: isize, y: isize) -> u8 {
+        self.grid.get(x, y).fetch()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+    use utils::*;
+
+    mod utils {
+        use super::*;
+
+        // Set the cell at the given index to alive and 8 neighbors
+        pub fn set_0b0001_0001(grid: &mut DynGrid, idx: usize) {
+            let cell = &mut grid.cells[idx];
+
+            while cell.neighbors() < 8 {
+                cell.add_neighbor();
+            }
+
+            cell.spawn();
+        }
+
+        // Check if the 2d index is correctly translated to a 1d index
+        pub fn test_2d_index_translation(w: usize, h: usize, idx: usize, x: isize, y: isize) {
+            let mut grid = DynGrid::new(w, h);
+            set_0b0001_0001(&mut grid, idx);
+
+            let actual = grid.get(x, y);
+            assert!(actual.alive());
+            assert!(actual.neighbors() == 8);
+
+            let expected = &grid.cells[idx];
+            assert_eq!(actual.fetch(), expected.fetch());
+        }
+    }
+
+    #[test]
+    fn test_create_grid() {
+        let grid = DynGrid::new(7, 3);
+        assert_eq!(grid.width(), 7);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.cells.len(), 21);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(DynGrid::try_new(7, 3).unwrap().cells.len(), 21);
+        assert!(DynGrid::try_new(0, 3).is_none());
+        assert!(DynGrid::try_new(MAX_CELLS + 1, 1).is_none());
+        assert!(DynGrid::try_new(usize::MAX, 2).is_none());
+    }
+
+    #[test]
+    fn test_get_cell_w_wrapping() {
+        const H: usize = 4;
+        const W: usize = 4;
+
+        /* Wrapping on the top left corner
+            3  1
+            2 [0][0][0][2]
+              [0][0][0][0]
+              [0][0][0][0]
+              [1][0][0][3]
+        */
+
+        test_2d_index_translation(W, H, 12, 0, -1); /* 1 */
+        test_2d_index_translation(W, H, 3, -1, 0); /* 2 */
+        test_2d_index_translation(W, H, 15, -1, -1); /* 3 */
+
+        /* Wrapping on the top right corner
+                        1  3
+              [2][0][0][0] 2
+              [0][0][0][0]
+              [0][0][0][0]
+              [3][0][0][1]
+        */
+
+        test_2d_index_translation(W, H, 15, 3, -1); /* 1 */
+        test_2d_index_translation(W, H, 0, 4, 0); /* 2 */
+        test_2d_index_translation(W, H, 12, 4, -1); /* 3 */
+
+        /* Wrapping on the bottom left corner
+              [1][0][0][3]
+              [0][0][0][0]
+              [0][0][0][0]
+            2 [0][0][0][2]
+            3  1
+        */
+
+        test_2d_index_translation(W, H, 0, 0, 4); /* 1 */
+        test_2d_index_translation(W, H, 15, -1, 3); /* 2 */
+        test_2d_index_translation(W, H, 3, -1, 4); /* 3 */
+
+        /* Wrapping on the bottom right corner
+              [1][0][0][3]
+              [0][0][0][0]
+              [0][0][0][0]
+              [2][0][0][0] 2
+                        3  1
+        */
+
+        test_2d_index_translation(W, H, 0, 4, 4); /* 1 */
+        test_2d_index_translation(W, H, 12, 4, 3); /* 2 */
+        test_2d_index_translation(W, H, 3, 3, 4); /* 3 */
+    }
+
+    #[test]
+    fn test_get_cell_w_wrapping_non_square() {
+        // 5 wide and 2 high: x wraps on 5, y wraps on 2
+        test_2d_index_translation(5, 2, 4, -1, 0);
+        test_2d_index_translation(5, 2, 5, 0, -1);
+        test_2d_index_translation(5, 2, 0, 5, 2);
+        test_2d_index_translation(5, 2, 9, -1, -1);
+    }
+
+    #[test]
+    fn test_copy_from() {
+        let other = DynGrid::new(6, 4);
+        other.spawn(1, 1);
+        other.spawn(2, 1);
+
+        let grid = DynGrid::new(6, 4);
+        grid.copy_from(&other);
+
+        for (cell, other) in grid.cells.iter().zip(other.cells.iter()) {
+            assert_eq!(cell.fetch(), other.fetch());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Grids must have the same size")]
+    fn test_copy_from_size_mismatch() {
+        DynGrid::new(6, 4).copy_from(&DynGrid::new(4, 6));
+    }
+
+    #[test]
+    fn test_generation_counter() {
+        let generator = DynGridGenerator::new(DynGrid::new(6, 4));
+        assert_eq!(generator.generation(), 0);
+
+        for _ in 0..7 {
+            generator.generate();
+        }
+
+        assert_eq!(generator.generation(), 7);
+    }
+
+    #[test]
+    fn test_dyn_generators() {
+        let grid = Grid::<5, 5>::new();
+        let dyn_grid = DynGrid::new(5, 5);
+
+        for (x, y) in [(1, 2), (2, 2), (3, 2)] {
+            grid.spawn(x, y);
+            dyn_grid.spawn(x, y);
+        }
+
+        let generators: Vec<Box<dyn DynGenerator + '_>> = vec![
+            Box::new(Generator::<5, 5>::new(&grid)),
+            Box::new(DynGridGenerator::new(dyn_grid)),
+        ];
+
+        for generator in generators.iter() {
+            assert_eq!((generator.width(), generator.height()), (5, 5));
+
+            generator.step();
+
+            // The horizontal blinker turns vertical around its center
+            for (x, y) in [(2, 1), (2, 2), (2, 3)] {
+                assert_eq!(generator.cell(x, y) & 1, 1);
+            }
+            for (x, y) in [(1, 2), (3, 2)] {
+                assert_eq!(generator.cell(x, y) & 1, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spawn_kill() {
+        let grid = DynGrid::new(4, 4);
+
+        grid.spawn(0, 0);
+        assert!(grid.get(0, 0).alive());
+
+        for (x, y) in grid.neighbor_coordinates(0, 0) {
+            assert_eq!(grid.get(x, y).neighbors(), 1);
+        }
+
+        grid.kill(0, 0);
+        assert!(!grid.get(0, 0).alive());
+
+        for (x, y) in grid.neighbor_coordinates(0, 0) {
+            assert_eq!(grid.get(x, y).neighbors(), 0);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+// Error returned by the loaders and other fallible functions in utils
+#[derive(Debug)]
+pub enum GolError {
+    // The file could not be read or written
+    Io(std::io::Error),
+    // Malformed CSV data on the given 1-based line
+    Csv {
+        line: usize,
+        message: String,
+    },
+    // Malformed run length encoded pattern
+    Rle(String),
+    // Malformed or unsupported binary grid data
+    Binary(String),
+    // The image could not be decoded or encoded
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+    // The data does not have the expected (width, height)
+    DimensionMismatch {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    // A coordinate lies outside the grid
+    OutOfBounds {
+        x: isize,
+        y: isize,
+    },
+    // More cells were requested than the grid holds
+    Population {
+        count: usize,
+        capacity: usize,
+    },
+}
+
+// Implement Display for GolError
+impl fmt::Display for GolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GolError::Io(error) => write!(f, "I/O error: {}", error),
+            GolError::Csv { line, message } => write!(f, "CSV error on line {}: {}", line, message),
+            GolError::Rle(message) => write!(f, "RLE error: {}", message),
+            GolError::Binary(message) => write!(f, "Binary format error: {}", message),
+            #[cfg(feature = "image")]
+            GolError::Image(error) => write!(f, "Image error: {}", error),
+            GolError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "Expected a {}x{} grid, found {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            GolError::OutOfBounds { x, y } => {
+                write!(f, "Coordinates ({}, {}) are outside the grid", x, y)
+            }
+            GolError::Population { count, capacity } => write!(
+                f,
+                "Cannot place {} cells in a grid of {} cells",
+                count, capacity
+            ),
+        }
+    }
+}
+
+// Implement Error for GolError
+impl std::error::Error for GolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GolError::Io(error) => Some(error),
+            #[cfg(feature = "image")]
+            GolError::Image(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// Implement From<io::Error> for GolError
+impl From<std::io::Error> for GolError {
+    fn from(error: std::io::Error) -> Self {
+        GolError::Io(error)
+    }
+}
+
+// Implement From<ImageError> for GolError
+// Image I/O failures are reported as Io like the other loaders
+#[cfg(feature = "image")]
+impl From<image::ImageError> for GolError {
+    fn from(error: image::ImageError) -> Self {
+        match error {
+            image::ImageError::IoError(error) => GolError::Io(error),
+            error => GolError::Image(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let io = GolError::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "missing.csv",
+        ));
+        assert_eq!(io.to_string(), "I/O error: missing.csv");
+        assert!(std::error::Error::source(&io).is_some());
+
+        let csv = GolError::Csv {
+            line: 3,
+            message: "Invalid cell \"x\"".to_string(),
+        };
+        assert_eq!(csv.to_string(), "CSV error on line 3: Invalid cell \"x\"");
+
+        let rle = GolError::Rle("Missing header".to_string());
+        assert_eq!(rle.to_string(), "RLE error: Missing header");
+
+        let binary = GolError::Binary("Bad magic bytes".to_string());
+        assert_eq!(binary.to_string(), "Binary format error: Bad magic bytes");
+
+        let dimensions = GolError::DimensionMismatch {
+            expected: (4, 4),
+            actual: (5, 3),
+        };
+        assert_eq!(dimensions.to_string(), "Expected a 4x4 grid, found 5x3");
+
+        let bounds = GolError::OutOfBounds { x: -1, y: 7 };
+        assert_eq!(
+            bounds.to_string(),
+            "Coordinates (-1, 7) are outside the grid"
+        );
+        assert!(std::error::Error::source(&bounds).is_none());
+
+        let population = GolError::Population {
+            count: 97,
+            capacity: 96,
+        };
+        assert_eq!(
+            population.to_string(),
+            "Cannot place 97 cells in a grid of 96 cells"
+        );
+    }
+}
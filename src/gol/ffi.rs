@@ -0,0 +1,157 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::gol::dyn_grid::{DynGrid, DynGridGenerator};
+
+// Opaque handle handed out to C callers
+// Const generics cannot cross the FFI boundary so the grid is runtime sized
+pub struct GolGrid {
+    generator: DynGridGenerator,
+}
+
+// Allocate a new grid of the given size
+// Returns null if the grid could not be created, e.g. because a dimension
+// is zero or the grid would have more than MAX_CELLS cells
+#[no_mangle]
+pub extern "C" fn gol_grid_new(w: usize, h: usize) -> *mut GolGrid {
+    let Some(grid) = DynGrid::try_new(w, h) else {
+        return std::ptr::null_mut();
+    };
+
+    let result = catch_unwind(|| {
+        let generator = DynGridGenerator::new(grid);
+        Box::into_raw(Box::new(GolGrid { generator }))
+    });
+
+    result.unwrap_or(std::ptr::null_mut())
+}
+
+// Spawn a cell at the given 2D coordinates
+// Returns 0 on success and -1 on failure
+// SAFETY: grid must be null or a live handle returned by gol_grid_new
+#[no_mangle]
+pub unsafe extern "C" fn gol_grid_spawn(grid: *mut GolGrid, x: isize, y: isize) -> i32 {
+    let Some(grid) = grid.as_ref() else {
+        return -1;
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| grid.generator.grid().spawn(x, y))) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+// Advance the grid by one generation
+// Returns 0 on success and -1 on failure
+// SAFETY: grid must be null or a live handle returned by gol_grid_new
+#[no_mangle]
+pub unsafe extern "C" fn gol_grid_step(grid: *mut GolGrid) -> i32 {
+    let Some(grid) = grid.as_ref() else {
+        return -1;
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| grid.generator.generate())) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+// Read the raw state byte of the cell at the given 2D coordinates
+// Returns the byte on success and -1 on failure
+// SAFETY: grid must be null or a live handle returned by gol_grid_new
+#[no_mangle]
+pub unsafe extern "C" fn gol_grid_cell(grid: *const GolGrid, x: isize, y: isize) -> i32 {
+    let Some(grid) = grid.as_ref() else {
+        return -1;
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| grid.generator.grid().get(x, y).fetch())) {
+        Ok(state) => state as i32,
+        Err(_) => -1,
+    }
+}
+
+// Release a grid returned by gol_grid_new. Passing null is a no-op
+// SAFETY: grid must be null or a live handle that is not used afterwards
+#[no_mangle]
+pub unsafe extern "C" fn gol_grid_free(grid: *mut GolGrid) {
+    if grid.is_null() {
+        return;
+    }
+
+    let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(grid))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const BLINKER_OFFSETS: [(isize, isize); 3] = [(0, 1), (1, 1), (2, 1)];
+
+    #[test]
+    fn test_lifecycle() {
+        unsafe {
+            let grid = gol_grid_new(5, 5);
+            assert!(!grid.is_null());
+
+            for (x, y) in BLINKER_OFFSETS {
+                assert_eq!(gol_grid_spawn(grid, x + 1, y + 1), 0);
+            }
+
+            // Horizontal blinker: center is alive with 2 neighbors
+            assert_eq!(gol_grid_cell(grid, 2, 2), 0b0000_0101);
+            assert_eq!(gol_grid_cell(grid, 2, 1) & 1, 0);
+
+            assert_eq!(gol_grid_step(grid), 0);
+
+            // Vertical blinker after one generation
+            assert_eq!(gol_grid_cell(grid, 2, 1) & 1, 1);
+            assert_eq!(gol_grid_cell(grid, 2, 2) & 1, 1);
+            assert_eq!(gol_grid_cell(grid, 2, 3) & 1, 1);
+            assert_eq!(gol_grid_cell(grid, 1, 2) & 1, 0);
+            assert_eq!(gol_grid_cell(grid, 3, 2) & 1, 0);
+
+            gol_grid_free(grid);
+        }
+    }
+
+    #[test]
+    fn test_single_row() {
+        unsafe {
+            let grid = gol_grid_new(5, 1);
+            assert!(!grid.is_null());
+
+            // Above and below wrap onto the row itself and are counted once
+            assert_eq!(gol_grid_spawn(grid, 2, 0), 0);
+            let neighbors: Vec<i32> = (0..5).map(|x| gol_grid_cell(grid, x, 0) >> 1).collect();
+            assert_eq!(neighbors, [0, 1, 0, 1, 0]);
+
+            assert_eq!(gol_grid_spawn(grid, 3, 0), 0);
+            assert_eq!(gol_grid_cell(grid, 2, 0), 0b0000_0011);
+            assert_eq!(gol_grid_cell(grid, 3, 0), 0b0000_0011);
+
+            // Two cells in a row die of underpopulation
+            assert_eq!(gol_grid_step(grid), 0);
+            assert!((0..5).all(|x| gol_grid_cell(grid, x, 0) == 0));
+
+            gol_grid_free(grid);
+        }
+    }
+
+    #[test]
+    fn test_invalid_handles() {
+        unsafe {
+            // Zero sized grids cannot be created
+            assert!(gol_grid_new(0, 5).is_null());
+
+            // Huge sizes are rejected before anything is allocated
+            assert!(gol_grid_new(usize::MAX, 2).is_null());
+            assert!(gol_grid_new(1 << 20, 1 << 20).is_null());
+
+            let null = std::ptr::null_mut();
+            assert_eq!(gol_grid_spawn(null, 0, 0), -1);
+            assert_eq!(gol_grid_step(null), -1);
+            assert_eq!(gol_grid_cell(null, 0, 0), -1);
+            gol_grid_free(null);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::gol::{generator::Generate, grid::Grid, rule::Rule, state_grid::StateGrid};
+
+// "Generations" rules: alive cells that do not survive start aging instead
+// of dying at once. An aging cell ages every generation until it reaches the
+// number of states and dies. Aging cells do not count as neighbors and
+// cannot host births. Brian's Brain is /2/3 in this notation
+pub struct GenerationsGenerator<'a, const H: usize, const W: usize> {
+    grid: &'a Grid<H, W>,
+    cache: StateGrid<H, W>,
+    rule: Rule,
+    states: u8,
+}
+
+impl<'a, const H: usize, const W: usize> GenerationsGenerator<'a, H, W> {
+    // States counts dead and alive, so a rule with 4 states has 2 aging states
+    pub fn new(grid: &'a Grid<H, W>, rule: Rule, states: u8) -> Self {
+        assert!(
+            (2..=9).contains(&states),
+            "States must be between 2 and 9, is {}",
+            states
+        );
+
+        Self {
+            grid,
+            cache: StateGrid::new(),
+            rule,
+            states,
+        }
+    }
+
+    pub fn generate(&self) {
+        self.cache.copy_from(self.grid);
+
+        let max_age = self.states - 2;
+
+        for y in 0..H {
+            for x in 0..W {
+                let x = x as isize;
+                let y = y as isize;
+
+                let cell = self.cache.get(x, y);
+
+                if *cell == 0b00000000 {
+                    continue;
+                }
+
+                let neighbor_count = cell.neighbors();
+
+                if cell.alive() {
+                    if !self.rule.survives(neighbor_count) {
+                        self.grid.kill(x, y);
+                        if max_age > 0 {
+                            self.grid.get(x, y).set_age(1);
+                        }
+                    }
+                } else if cell.age() > 0 {
+                    let age = cell.age() + 1;
+                    self.grid
+                        .get(x, y)
+                        .set_age(if age > max_age { 0 } else { age });
+                } else if self.rule.born(neighbor_count) {
+                    self.grid.spawn(x, y);
+                }
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+// Implement Generate for GenerationsGenerator
+impl<'a, const H: usize, const W: usize> Generate<H, W> for GenerationsGenerator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    // 23/3/4: Survive on 2 or 3, born on 3, 4 states
+    const RULE: Rule = Rule::new(&[3], &[2, 3]);
+    const STATES: u8 = 4;
+
+    #[test]
+    fn test_aging() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn(3, 3);
+
+        let generator = GenerationsGenerator::<8, 8>::new(&grid, RULE, STATES);
+
+        // Alive -> age 1 -> age 2 -> dead
+        for age in [1, 2, 0] {
+            generator.generate();
+
+            let cell = grid.get(3, 3);
+            assert!(!cell.alive());
+            assert_eq!(cell.age(), age);
+            assert!(grid.verify_neighbors());
+        }
+
+        assert_eq!(grid.to_bytes(), vec![0; 64]);
+    }
+
+    #[test]
+    fn test_aging_cells_cannot_host_births() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((1, 1), &[(0, 0), (1, 0), (2, 0)]);
+        grid.get(2, 2).set_age(1);
+
+        let generator = GenerationsGenerator::<8, 8>::new(&grid, RULE, STATES);
+        generator.generate();
+
+        // Three alive neighbors, but the cell keeps aging
+        let cell = grid.get(2, 2);
+        assert!(!cell.alive());
+        assert_eq!(cell.age(), 2);
+
+        // The cell on the other side of the row is born as usual
+        assert!(grid.get(2, 0).alive());
+    }
+}
@@ -1,52 +1,1272 @@
-use crate::gol::{cell::Cell, grid::Grid};
+use crate::gol::{
+    cell::Cell,
+    dyn_grid::DynGenerator,
+    error::GolError,
+    grid::{circular_mean, Grid},
+    partition::{partition, ChunkStrategy, Region},
+    rule::Rule,
+    state_grid::StateGrid,
+};
 
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::File,
+    io::{BufWriter, Read, Write},
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread::JoinHandle,
+};
+
+// Generations classify_regions runs at most to let the board settle
+pub const SETTLE_GENERATIONS: usize = 10_000;
+
+// Common interface of the const sized generators
+// step is the canonical way to advance a grid by one generation
+pub trait Generate<const H: usize, const W: usize> {
+    // Advance the grid by one generation
+    fn step(&self);
+
+    // The grid being generated
+    fn grid(&self) -> &Grid<H, W>;
+}
+
+// Owned copy of a grid's state and the generation it was taken at
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridSnapshot {
+    pub bytes: Vec<u8>,
+    pub generation: usize,
+}
+
+// Change to a single cell planned for the next generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Spawn,
+    Kill,
+}
+
+// Number of cells spawned and killed during one generation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepStats {
+    pub births: usize,
+    pub deaths: usize,
+}
+
+// Grid advanced by a generator: borrowed for single-threaded use
+// or shared with other threads through an Arc
+enum GridRef<'a, const H: usize, const W: usize> {
+    Borrowed(&'a Grid<H, W>),
+    Shared(Arc<Grid<H, W>>),
+}
+
+// Implement Deref for GridRef
+impl<'a, const H: usize, const W: usize> Deref for GridRef<'a, H, W> {
+    type Target = Grid<H, W>;
+
+    fn deref(&self) -> &Grid<H, W> {
+        match self {
+            GridRef::Borrowed(grid) => grid,
+            GridRef::Shared(grid) => grid,
+        }
+    }
+}
 
 pub struct Generator<'a, const H: usize, const W: usize> {
-    grid: Arc<&'a Grid<H, W>>,
-    cache: Grid<H, W>,
+    grid: GridRef<'a, H, W>,
+    cache: StateGrid<H, W>,
+    rule: Rule,
+    generation: AtomicUsize,
 }
 
 impl<'a , const H: usize, const W: usize> Generator<'a , H, W> {
-    pub fn new(grid: Arc<&'a Grid<H, W>>) -> Self {
+    pub fn new(grid: &'a Grid<H, W>) -> Self {
+        Self::with_rule(grid, Rule::conway())
+    }
+
+    // Create a generator for a grid that is shared with other threads
+    pub fn new_shared(grid: Arc<Grid<H, W>>) -> Self {
+        Self {
+            grid: GridRef::Shared(grid),
+            cache: StateGrid::new(),
+            rule: Rule::conway(),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    // Create a generator applying the given birth/survival rule
+    pub fn with_rule(grid: &'a Grid<H, W>, rule: Rule) -> Self {
+        Self {
+            grid: GridRef::Borrowed(grid),
+            cache: StateGrid::new(),
+            rule,
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    // Create a generator whose cache cells use the given load and store orderings
+    // Only the state bytes are copied into the cache, so its orderings are kept
+    pub fn with_orderings(grid: &'a Grid<H, W>, fetch: Ordering, store: Ordering) -> Self {
         Self {
-            grid: grid,
-            cache: Grid::new(),
+            grid: GridRef::Borrowed(grid),
+            cache: StateGrid::with_orderings(fetch, store),
+            rule: Rule::conway(),
+            generation: AtomicUsize::new(0),
         }
     }
 
     pub fn generate(&self) {
-        unsafe {
-            self.cache.unsafe_copy_from(&self.grid);
+        self.generate_counted();
+    }
+
+    // Generate one generation and, in debug builds, check every neighbor count afterwards
+    // Panics with the first inconsistent cell. Release builds skip the check
+    pub fn generate_checked(&self) {
+        self.generate_counted();
+
+        if cfg!(debug_assertions) {
+            if let Some((x, y)) = self.grid.neighbor_mismatch() {
+                panic!(
+                    "Neighbor count of cell ({}, {}) is {}, expected {} after generation {}",
+                    x,
+                    y,
+                    self.grid.get(x, y).neighbors(),
+                    self.grid.live_neighbor_mask(x, y).count_ones(),
+                    self.generation()
+                );
+            }
         }
+    }
+
+    // Generate one generation and count the cells spawned and killed
+    pub fn step_with_stats(&mut self) -> StepStats {
+        self.generate_counted()
+    }
 
-        for x in 0..H {
-            for y in 0..W {
-                let x = x as isize;
-                let y = y as isize;
+    fn generate_counted(&self) -> StepStats {
+        self.cache.copy_from(&self.grid);
 
-                let cell = self.cache.get(x, y);
+        let stats = self.generate_region(&Region {
+            rows: 0..H,
+            cols: 0..W,
+        });
+        self.generation.fetch_add(1, Ordering::Relaxed);
 
-                if *cell == 0b00000000 {
-                    continue;
-                }
+        stats
+    }
+
+    // Generate one generation with the rows split between the given number of threads
+    // Every thread only reads from the shared cache so the result matches generate
+    pub fn generate_parallel(&self, threads: usize) {
+        self.generate_parallel_with(threads, ChunkStrategy::Rows);
+    }
+
+    // Generate one generation with the board partitioned between the threads by the strategy
+    // Threads at a region border update the same neighbor counts, but every update is a
+    // single read-modify-write increment or decrement, so the order they land in never
+    // changes the final counts and no guard band between regions is needed
+    pub fn generate_parallel_with(&self, threads: usize, strategy: ChunkStrategy) {
+        assert!(threads > 0, "Thread count must be at least 1");
+
+        self.cache.copy_from(&self.grid);
+
+        std::thread::scope(|scope| {
+            for region in partition::<H, W>(strategy, threads) {
+                scope.spawn(move || {
+                    self.generate_region(&region);
+                });
+            }
+        });
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Generate one generation scanning columns top to bottom instead of rows
+    // The result is identical to generate, only the memory access order differs
+    pub fn generate_column_major(&self) {
+        self.cache.copy_from(&self.grid);
+
+        let mut stats = StepStats::default();
+
+        for x in 0..W {
+            for y in 0..H {
+                self.apply_rule(x as isize, y as isize, &mut stats);
+            }
+        }
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Generate one generation visiting the cells in Z-order, see Grid::morton_coordinates
+    // The result is identical to generate, only the memory access order differs
+    pub fn generate_morton(&self) {
+        self.cache.copy_from(&self.grid);
+
+        let mut stats = StepStats::default();
+
+        for (x, y) in self.grid.morton_coordinates() {
+            self.apply_rule(x, y, &mut stats);
+        }
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
 
+    // Work out every spawn and kill of the next generation without changing the grid
+    // Planning only reads, so it can be split between threads freely
+    pub fn plan_step(&self) -> Vec<(isize, isize, Action)> {
+        self.grid
+            .enumerate_cells()
+            .filter(|(_, cell)| !(**cell == 0b00000000 && !self.rule.born(0)))
+            .filter_map(|((x, y), cell)| {
                 let neighbor_count = cell.neighbors();
 
-                if cell.alive() {
-                    if neighbor_count < 2 || neighbor_count > 3 {
-                        self.grid.kill(x, y);
-                    }
-                } else {
-                    if neighbor_count == 3 {
-                        self.grid.spawn(x, y);
-                    }
+                match cell.alive() {
+                    true if !self.rule.survives(neighbor_count) => Some((x, y, Action::Kill)),
+                    false if self.rule.born(neighbor_count) => Some((x, y, Action::Spawn)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    // Apply a plan from plan_step to the grid and advance the generation counter
+    pub fn apply_step(&self, plan: &[(isize, isize, Action)]) {
+        for (x, y, action) in plan {
+            match action {
+                Action::Spawn => self.grid.spawn(*x, *y),
+                Action::Kill => self.grid.kill(*x, *y),
+            }
+        }
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Apply the rule to the given region reading the previous state from the cache
+    fn generate_region(&self, region: &Region) -> StepStats {
+        let mut stats = StepStats::default();
+
+        for y in region.rows.clone() {
+            for x in region.cols.clone() {
+                self.apply_rule(x as isize, y as isize, &mut stats);
+            }
+        }
+
+        stats
+    }
+
+    #[inline]
+    // Spawn or kill a single cell based on its state in the cache
+    fn apply_rule(&self, x: isize, y: isize, stats: &mut StepStats) {
+        if self.cache.fetch(x, y) == 0b00000000 && !self.rule.born(0) {
+            return;
+        }
+
+        let neighbor_count = self.cache.neighbors(x, y);
+
+        if self.cache.alive(x, y) {
+            if !self.rule.survives(neighbor_count) {
+                self.grid.kill(x, y);
+                stats.deaths += 1;
+            }
+        } else if self.rule.born(neighbor_count) {
+            self.grid.spawn(x, y);
+            stats.births += 1;
+        }
+    }
+
+    // Number of generations generated so far
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    // Save the current state and generation number
+    pub fn checkpoint(&self) -> GridSnapshot {
+        GridSnapshot {
+            bytes: self.grid.to_bytes(),
+            generation: self.generation(),
+        }
+    }
+
+    // Store a checkpoint back into the grid and reset the generation number
+    pub fn restore(&mut self, snapshot: &GridSnapshot) {
+        assert_eq!(
+            snapshot.bytes.len(),
+            H * W,
+            "Snapshot must have H * W bytes"
+        );
+
+        for (cell, state) in self.grid.iter().zip(snapshot.bytes.iter()) {
+            cell.store(*state);
+        }
+
+        *self.generation.get_mut() = snapshot.generation;
+    }
+
+    // Write the grid with write_binary followed by the generation number as a little endian u64
+    pub fn write_session(&self, mut w: impl Write) -> std::io::Result<()> {
+        self.grid.write_binary(&mut w)?;
+        w.write_all(&(self.generation() as u64).to_le_bytes())
+    }
+
+    // Load a session written by write_session into the grid and the generation counter
+    pub fn read_session(&mut self, mut r: impl Read) -> Result<(), GolError> {
+        let grid = Grid::<H, W>::read_binary(&mut r)?;
+
+        let mut generation = [0u8; 8];
+        r.read_exact(&mut generation)?;
+
+        self.restore(&GridSnapshot {
+            bytes: grid.to_bytes(),
+            generation: u64::from_le_bytes(generation) as usize,
+        });
+
+        Ok(())
+    }
+
+    // Whether the next generation equals the current one
+    // The grid and generation number are restored afterwards, so the call has no side effects
+    pub fn is_still_life(&mut self) -> bool {
+        let snapshot = self.checkpoint();
+        self.generate();
+
+        let unchanged = self.grid.to_bytes() == snapshot.bytes;
+        self.restore(&snapshot);
+
+        unchanged
+    }
+
+    // Generate up to n generations, stopping early once the grid is empty
+    // Returns the number of generations actually generated
+    pub fn run_generations(&mut self, n: usize) -> usize {
+        for generation in 0..n {
+            if self.grid.population() == 0 {
+                return generation;
+            }
+
+            self.generate();
+        }
+
+        n
+    }
+
+    // Generate n generations and record the population after each one
+    pub fn run_collecting_population(&mut self, n: usize) -> Vec<usize> {
+        (0..n)
+            .map(|_| {
+                self.generate();
+                self.grid.population()
+            })
+            .collect()
+    }
+
+    // Generate n generations and return the highest population seen,
+    // including the population before the first generation
+    pub fn run_peak_population(&mut self, n: usize) -> usize {
+        let initial = self.grid.population();
+        self.run_collecting_population(n)
+            .into_iter()
+            .fold(initial, usize::max)
+    }
+
+    // Generate n generations and write the grid to "{path_prefix}_{generation}.golb"
+    // with write_binary after every `every` generations
+    pub fn run_with_autosave(
+        &mut self,
+        n: usize,
+        every: usize,
+        path_prefix: &str,
+    ) -> std::io::Result<()> {
+        assert!(every > 0, "Autosave interval must be at least 1");
+
+        for _ in 0..n {
+            self.generate();
+
+            let generation = self.generation();
+            if generation % every == 0 {
+                let file = File::create(format!("{}_{}.golb", path_prefix, generation))?;
+                let mut writer = BufWriter::new(file);
+
+                // Flush explicitly, dropping the writer would swallow a failed write
+                self.grid.write_binary(&mut writer)?;
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Generate until a state repeats and return the generation, counted from this call,
+    // at which the repeating cycle was first entered. A still life is a cycle of length 1
+    // States are compared by content_hash. Returns None if nothing repeats within max_gen
+    pub fn generations_to_stabilize(&mut self, max_gen: usize) -> Option<usize> {
+        let mut seen = HashMap::new();
+        seen.insert(self.grid.content_hash(), 0);
+
+        for generation in 1..=max_gen {
+            self.generate();
+
+            if let Some(start) = seen.insert(self.grid.content_hash(), generation) {
+                return Some(start);
+            }
+        }
+
+        None
+    }
+
+    // Move the generator onto a new thread that generates and sends a snapshot
+    // of the grid after each of the given number of generations
+    // Stops early if the receiver is dropped
+    pub fn spawn_runner(self, tx: Sender<Vec<u8>>, generations: usize) -> JoinHandle<()>
+    where
+        'a: 'static,
+    {
+        std::thread::spawn(move || {
+            for _ in 0..generations {
+                self.generate();
+
+                if tx.send(self.grid.to_bytes()).is_err() {
+                    break;
                 }
             }
+        })
+    }
+
+    // Lazily generate and snapshot one generation per call to next
+    pub fn iter_generations(&mut self) -> impl Iterator<Item = Vec<u8>> + use<'_, 'a, H, W> {
+        std::iter::from_fn(move || {
+            self.generate();
+            Some(self.grid.to_bytes())
+        })
+    }
+
+    // Generate until the grid has not changed for `window` consecutive samples
+    // Returns the number of generations run, or max_gen if it never stagnated
+    // Population alone would flag oscillators, so the full state is compared
+    pub fn run_until_stagnant(&mut self, window: usize, max_gen: usize) -> usize {
+        assert!(window > 0, "Stagnation window must be at least 1");
+
+        let mut samples = VecDeque::with_capacity(window);
+        samples.push_back(self.sample());
+
+        for generation in 1..=max_gen {
+            self.generate();
+
+            if samples.len() == window {
+                samples.pop_front();
+            }
+            samples.push_back(self.sample());
+
+            if samples.len() == window && samples.iter().all(|sample| *sample == samples[0]) {
+                return generation;
+            }
+        }
+
+        max_gen
+    }
+
+    // How far the live cells have traveled since the given initial state bytes
+    // Compares the toroidal centroids, so a wrapped pattern reports the short way round
+    pub fn net_displacement(&self, initial: &[u8]) -> (isize, isize) {
+        assert_eq!(initial.len(), H * W, "Initial state must have H * W bytes");
+
+        let before = initial
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| *state & 1 == 1)
+            .map(|(i, _)| i);
+
+        let after = self
+            .grid
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.alive())
+            .map(|(i, _)| i);
+
+        let before: Vec<usize> = before.collect();
+        let after: Vec<usize> = after.collect();
+
+        let dx = axis_displacement(before.iter().map(|i| i % W), after.iter().map(|i| i % W), W);
+        let dy = axis_displacement(before.iter().map(|i| i / W), after.iter().map(|i| i / W), H);
+
+        (dx, dy)
+    }
+
+    // Period of every group of connected live cells, keyed by its first cell in row-major order
+    // The board is first generated until its state repeats, at most SETTLE_GENERATIONS
+    // generations, so transients do not get classified. Each group of the settled board is
+    // then copied onto an empty grid and stepped on its own until it repeats
+    // Groups that do not repeat within max_period generations are left out
+    pub fn classify_regions(&mut self, max_period: usize) -> HashMap<(isize, isize), usize> {
+        self.generations_to_stabilize(SETTLE_GENERATIONS);
+
+        let mut periods = HashMap::new();
+
+        for component in self.grid.components() {
+            let isolated = Grid::<H, W>::new();
+            isolated.spawn_batch(&component);
+
+            let initial = isolated.to_bytes();
+            let generator = Generator::with_rule(&isolated, self.rule);
+
+            let period = (1..=max_period).find(|_| {
+                generator.generate();
+                isolated.to_bytes() == initial
+            });
+
+            if let Some(period) = period {
+                periods.insert(component[0], period);
+            }
         }
+
+        periods
+    }
+
+    // Population and state hash of the grid
+    fn sample(&self) -> (usize, u64) {
+        (self.grid.population(), self.grid.content_hash())
     }
 
     pub fn grid(&self) -> &Grid<H, W> {
         &self.grid
     }
 }
+
+// Implement Generate for Generator
+impl<'a, const H: usize, const W: usize> Generate<H, W> for Generator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+// Implement DynGenerator for Generator
+impl<'a, const H: usize, const W: usize> DynGenerator for Generator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn width(&self) -> usize {
+        W
+    }
+
+    fn height(&self) -> usize {
+        H
+    }
+
+    fn cell(&self, x: isize, y: isize) -> u8 {
+        self.grid.get(x, y).fetch()
+    }
+}
+
+// Shortest signed distance between the circular means of two position sets
+fn axis_displacement(
+    before: impl Iterator<Item = usize>,
+    after: impl Iterator<Item = usize>,
+    n: usize,
+) -> isize {
+    let (Some(before), Some(after)) = (circular_mean(before, n), circular_mean(after, n)) else {
+        return 0;
+    };
+
+    let n = n as isize;
+    let shift = ((after - before) / std::f64::consts::TAU * n as f64).round() as isize;
+    let shift = shift.rem_euclid(n);
+
+    if shift > n / 2 {
+        shift - n
+    } else {
+        shift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    pub const BLOCK_OFFSETS: [(isize, isize); 4] = [(0, 0), (1, 0), (0, 1), (1, 1)];
+    pub const BLINKER_OFFSETS: [(isize, isize); 3] = [(0, 1), (1, 1), (2, 1)];
+    pub const GLIDER_OFFSETS: [(isize, isize); 5] = [(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)];
+
+    #[test]
+    fn test_generate_blinker() {
+        let grid = Grid::<5, 5>::new();
+        grid.spawn_shape((1, 1), &BLINKER_OFFSETS);
+
+        let generator = Generator::<5, 5>::new(&grid);
+        generator.generate();
+
+        // The horizontal blinker turns vertical around its center
+        assert!(grid.get(2, 1).alive());
+        assert!(grid.get(2, 2).alive());
+        assert!(grid.get(2, 3).alive());
+        assert!(!grid.get(1, 2).alive());
+        assert!(!grid.get(3, 2).alive());
+        assert_eq!(grid.population(), 3);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_run_generations_extinction() {
+        let grid = Grid::<8, 8>::new();
+
+        // A diagonal of three cells shrinks to one cell and then dies
+        grid.spawn_shape((2, 2), &[(0, 0), (1, 1), (2, 2)]);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.run_generations(10), 2);
+        assert_eq!(generator.generation(), 2);
+        assert_eq!(grid.population(), 0);
+    }
+
+    #[test]
+    fn test_run_generations_blinker() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &BLINKER_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.run_generations(10), 10);
+        assert_eq!(grid.population(), 3);
+    }
+
+    #[test]
+    fn test_generate_column_major() {
+        const H: usize = 1000;
+        const W: usize = 100;
+        const GENERATIONS: usize = 5;
+
+        let grid = Grid::<H, W>::new();
+        randomize_grid_seeded(&grid, 1862, 0.4);
+        let reference = grid.clone();
+
+        let generator = Generator::<H, W>::new(&grid);
+        let reference_generator = Generator::<H, W>::new(&reference);
+
+        for _ in 0..GENERATIONS {
+            generator.generate_column_major();
+            reference_generator.generate();
+        }
+
+        assert!(grid == reference);
+        assert_eq!(generator.generation(), GENERATIONS);
+    }
+
+    #[test]
+    fn test_generate_morton() {
+        const H: usize = 256;
+        const W: usize = 256;
+        const GENERATIONS: usize = 5;
+
+        let grid = Grid::<H, W>::new();
+        randomize_grid_seeded(&grid, 1894, 0.4);
+        let reference = grid.clone();
+
+        let generator = Generator::<H, W>::new(&grid);
+        let reference_generator = Generator::<H, W>::new(&reference);
+
+        for _ in 0..GENERATIONS {
+            generator.generate_morton();
+            reference_generator.generate();
+        }
+
+        assert!(grid == reference);
+        assert_eq!(generator.generation(), GENERATIONS);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Neighbor count of cell (6, 6) is 1, expected 0")]
+    fn test_generate_checked() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((1, 1), &BLOCK_OFFSETS);
+
+        let generator = Generator::<8, 8>::new(&grid);
+        generator.generate_checked();
+
+        // An isolated dead cell claiming a neighbor is not touched by the rule
+        grid.get(6, 6).store(0b0000_0010);
+
+        generator.generate();
+        assert_eq!(grid.population(), 4);
+
+        generator.generate_checked();
+    }
+
+    #[test]
+    fn test_generations_to_stabilize() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &BLOCK_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.generations_to_stabilize(10), Some(0));
+
+        // A blinker is already in its cycle
+        grid.clear();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+        assert_eq!(generator.generations_to_stabilize(10), Some(0));
+
+        // A glider on a torus comes back after 4 * 8 generations
+        grid.clear();
+        grid.spawn_shape((0, 0), &GLIDER_OFFSETS);
+        assert_eq!(generator.generations_to_stabilize(31), None);
+
+        // The R-pentomino needs 1103 generations on an unbounded plane. On the
+        // torus its gliders wrap around into the debris and it settles sooner
+        let grid = Grid::<128, 128>::new();
+        grid.spawn_shape((64, 64), &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]);
+
+        let mut generator = Generator::<128, 128>::new(&grid);
+        assert_eq!(generator.generations_to_stabilize(5000), Some(758));
+    }
+
+    #[test]
+    fn test_run_with_autosave() {
+        let prefix = std::env::temp_dir().join("gol_test_run_with_autosave");
+        let prefix = prefix.to_str().unwrap();
+
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((1, 1), &GLIDER_OFFSETS);
+
+        let reference = grid.clone();
+        let mut reference_generator = Generator::<10, 10>::new(&reference);
+
+        let mut generator = Generator::<10, 10>::new(&grid);
+        generator.run_with_autosave(10, 5, prefix).unwrap();
+
+        for generation in [5, 10] {
+            reference_generator.run_generations(5);
+
+            let path = format!("{}_{}.golb", prefix, generation);
+            let file = std::fs::File::open(&path).unwrap();
+            let loaded = Grid::<10, 10>::read_binary(file).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert!(loaded == reference);
+        }
+
+        assert!(!std::path::Path::new(&format!("{}_1.golb", prefix)).exists());
+    }
+
+    #[test]
+    fn test_spawn_runner() {
+        let grid = Arc::new(Grid::<8, 8>::new());
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+        let horizontal = grid.to_bytes();
+
+        let generator = Generator::<8, 8>::new_shared(Arc::clone(&grid));
+        generator.generate();
+        let vertical = grid.to_bytes();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = generator.spawn_runner(tx, 5);
+
+        // The blinker alternates between its phases, back to horizontal first
+        let snapshots: Vec<Vec<u8>> = rx.iter().collect();
+        handle.join().unwrap();
+
+        assert_eq!(snapshots.len(), 5);
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            let expected = if i % 2 == 0 { &horizontal } else { &vertical };
+            assert_eq!(snapshot, expected);
+        }
+    }
+
+    #[test]
+    fn test_borrowed_and_shared() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::new(&grid);
+        generator.generate();
+        assert!(grid.get(4, 3).alive());
+
+        // A shared grid can be generated on another thread and read on this one
+        let shared = Arc::new(Grid::<8, 8>::new());
+        shared.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::new_shared(Arc::clone(&shared));
+        std::thread::spawn(move || generator.generate())
+            .join()
+            .unwrap();
+
+        assert!(*shared == grid);
+    }
+
+    #[test]
+    fn test_classify_regions() {
+        let grid = Grid::<16, 16>::new();
+        grid.spawn_shape((2, 2), &BLOCK_OFFSETS);
+        grid.spawn_shape((9, 9), &BLINKER_OFFSETS);
+
+        // A glider never returns to its starting cells
+        grid.spawn_shape((2, 10), &GLIDER_OFFSETS);
+
+        let mut generator = Generator::<16, 16>::new(&grid);
+        let periods = generator.classify_regions(8);
+
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[&(2, 2)], 1);
+        assert_eq!(periods[&(9, 10)], 2);
+
+        // The glider needs 64 generations to wrap around and repeat the board
+        assert_eq!(generator.generation(), 64);
+    }
+
+    #[test]
+    fn test_classify_regions_unsettled() {
+        let grid = Grid::<16, 16>::new();
+
+        // Three cells of a block turn into the block after one generation,
+        // but the tromino on its own never returns to its initial state
+        grid.spawn_shape((2, 2), &[(0, 0), (1, 0), (0, 1)]);
+        grid.spawn_shape((9, 9), &BLINKER_OFFSETS);
+
+        let mut generator = Generator::<16, 16>::new(&grid);
+        let periods = generator.classify_regions(8);
+
+        let mut found: Vec<usize> = periods.values().copied().collect();
+        found.sort();
+
+        assert_eq!(found, [1, 2]);
+        assert_eq!(periods[&(2, 2)], 1);
+        assert_eq!(grid.population(), 7);
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((1, 1), &GLIDER_OFFSETS);
+
+        let mut generator = Generator::<10, 10>::new(&grid);
+        generator.run_generations(5);
+
+        let mut session = Vec::new();
+        generator.write_session(&mut session).unwrap();
+
+        let loaded = Grid::<10, 10>::new();
+        let mut loaded_generator = Generator::<10, 10>::new(&loaded);
+        loaded_generator.read_session(session.as_slice()).unwrap();
+
+        assert!(loaded == grid);
+        assert_eq!(loaded_generator.generation(), 5);
+
+        // Both continue in lockstep
+        generator.generate();
+        loaded_generator.generate();
+        assert!(loaded == grid);
+        assert_eq!(loaded_generator.generation(), 6);
+
+        // A session without its generation number is truncated
+        assert!(matches!(
+            loaded_generator.read_session(&session[..session.len() - 8]),
+            Err(GolError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_still_life() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &BLOCK_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert!(generator.is_still_life());
+        assert_eq!(generator.generation(), 0);
+
+        grid.clear();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+        let before = grid.to_bytes();
+
+        assert!(!generator.is_still_life());
+        assert_eq!(grid.to_bytes(), before);
+        assert_eq!(generator.generation(), 0);
+    }
+
+    #[test]
+    fn test_plan_step() {
+        for seed in 0..20 {
+            let grid = Grid::<16, 16>::new();
+            randomize_grid_seeded(&grid, seed, 0.4);
+            let reference = grid.clone();
+
+            let generator = Generator::<16, 16>::new(&grid);
+            let reference_generator = Generator::<16, 16>::new(&reference);
+
+            for _ in 0..10 {
+                let plan = generator.plan_step();
+                generator.apply_step(&plan);
+                reference_generator.generate();
+
+                assert!(grid == reference, "Seed {} diverged", seed);
+            }
+        }
+
+        // A blinker kills its two ends and spawns two new ones
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(
+            generator.plan_step(),
+            [
+                (4, 3, Action::Spawn),
+                (3, 4, Action::Kill),
+                (5, 4, Action::Kill),
+                (4, 5, Action::Spawn),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_step_with_stats() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+
+        for _ in 0..4 {
+            let stats = generator.step_with_stats();
+            assert_eq!(
+                stats,
+                StepStats {
+                    births: 2,
+                    deaths: 2
+                }
+            );
+            assert!(grid.get(4, 4).alive());
+        }
+
+        assert_eq!(generator.generation(), 4);
+    }
+
+    #[test]
+    fn test_run_collecting_population() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.run_collecting_population(4), [3, 3, 3, 3]);
+
+        // A beacon alternates between 6 and 8 cells
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &BLOCK_OFFSETS);
+        grid.spawn_shape((4, 4), &BLOCK_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.run_collecting_population(5), [6, 8, 6, 8, 6]);
+        assert_eq!(generator.generation(), 5);
+    }
+
+    #[test]
+    fn test_iter_generations() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &BLINKER_OFFSETS);
+        let initial = grid.to_bytes();
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+        let snapshots: Vec<Vec<u8>> = generator.iter_generations().take(3).collect();
+
+        assert_eq!(snapshots.len(), 3);
+        assert_ne!(snapshots[0], initial);
+        assert_eq!(snapshots[1], initial);
+        assert_eq!(snapshots[2], snapshots[0]);
+        assert_eq!(generator.generation(), 3);
+    }
+
+    #[test]
+    fn test_run_until_stagnant_block() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLOCK_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+
+        // The block never changes, so the window fills as fast as possible
+        assert_eq!(generator.run_until_stagnant(3, 100), 2);
+        assert_eq!(generator.grid().population(), 4);
+    }
+
+    #[test]
+    fn test_run_until_stagnant_blinker() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let mut generator = Generator::<8, 8>::new(&grid);
+
+        // Population stays at 3 but the state keeps flipping
+        assert_eq!(generator.run_until_stagnant(3, 100), 100);
+        assert_eq!(generator.grid().population(), 3);
+    }
+
+    #[test]
+    fn test_generation_counter() {
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::new(&grid);
+        assert_eq!(generator.generation(), 0);
+
+        for _ in 0..7 {
+            generator.generate();
+        }
+
+        assert_eq!(generator.generation(), 7);
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let grid = Grid::<12, 12>::new();
+        grid.spawn_shape((1, 1), &GLIDER_OFFSETS);
+
+        let mut generator = Generator::<12, 12>::new(&grid);
+        generator.run_generations(5);
+
+        let checkpoint = generator.checkpoint();
+        assert_eq!(checkpoint.generation, 5);
+
+        generator.run_generations(5);
+        assert!(generator.grid().to_bytes() != checkpoint.bytes);
+
+        generator.restore(&checkpoint);
+        assert_eq!(generator.grid().to_bytes(), checkpoint.bytes);
+        assert_eq!(generator.generation(), 5);
+        assert_eq!(generator.checkpoint(), checkpoint);
+    }
+
+    #[test]
+    fn test_net_displacement_glider() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((2, 2), &GLIDER_OFFSETS);
+
+        let generator = Generator::<10, 10>::new(&grid);
+        let initial = grid.to_bytes();
+
+        for _ in 0..4 {
+            generator.generate();
+        }
+
+        assert_eq!(generator.net_displacement(&initial), (1, 1));
+    }
+
+    #[test]
+    fn test_net_displacement_across_seam() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((7, 7), &GLIDER_OFFSETS);
+
+        let generator = Generator::<10, 10>::new(&grid);
+        let initial = grid.to_bytes();
+
+        // Two periods carry the glider over the bottom right corner
+        for _ in 0..8 {
+            generator.generate();
+        }
+
+        assert_eq!(generator.net_displacement(&initial), (2, 2));
+    }
+
+    #[test]
+    fn test_generate_parallel() {
+        let grid = Grid::<32, 32>::new();
+        let reference = Grid::<32, 32>::new();
+
+        for (x, y) in [
+            (3, 4),
+            (4, 4),
+            (5, 4),
+            (5, 3),
+            (4, 2),
+            (20, 20),
+            (21, 20),
+            (22, 20),
+        ] {
+            grid.spawn(x, y);
+            reference.spawn(x, y);
+        }
+
+        let generator = Generator::<32, 32>::new(&grid);
+        let reference_generator = Generator::<32, 32>::new(&reference);
+
+        for _ in 0..20 {
+            generator.generate_parallel(3);
+            reference_generator.generate();
+            assert_eq!(grid.to_bytes(), reference.to_bytes());
+        }
+
+        assert_eq!(generator.generation(), 20);
+    }
+
+    #[test]
+    fn test_generate_parallel_seeded() {
+        let grid = Grid::<50, 37>::new();
+        randomize_grid_seeded(&grid, 1838, 0.35);
+
+        let reference = grid.clone();
+
+        let generator = Generator::<50, 37>::new(&grid);
+        let reference_generator = Generator::<50, 37>::new(&reference);
+
+        for _ in 0..25 {
+            generator.generate_parallel(4);
+            reference_generator.generate();
+        }
+
+        assert_eq!(grid.to_bytes(), reference.to_bytes());
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_generate_parallel_remainder_rows() {
+        // 100 rows do not split evenly between 3 threads
+        let grid = Grid::<100, 16>::new();
+        randomize_grid_seeded(&grid, 1887, 0.4);
+
+        // A vertical blinker through the last row flips it every generation
+        grid.spawn_batch(&[(8, 98), (8, 99), (8, 0)]);
+
+        let reference = grid.clone();
+        let generator = Generator::<100, 16>::new(&grid);
+        let reference_generator = Generator::<100, 16>::new(&reference);
+
+        for _ in 0..10 {
+            let last_row = grid.row(99).map(Cell::fetch).collect::<Vec<_>>();
+
+            generator.generate_parallel(3);
+            reference_generator.generate();
+
+            assert!(grid == reference);
+            assert_ne!(grid.row(99).map(Cell::fetch).collect::<Vec<_>>(), last_row);
+        }
+
+        assert_eq!(
+            partition::<100, 16>(ChunkStrategy::Rows, 3)
+                .last()
+                .unwrap()
+                .rows
+                .end,
+            100
+        );
+    }
+
+    #[test]
+    fn test_generate_parallel_stress() {
+        const H: usize = 64;
+        const W: usize = 64;
+
+        let initial = Grid::<H, W>::new();
+        randomize_grid_seeded(&initial, 1847, 0.6);
+
+        let reference = initial.clone();
+        let reference_generator = Generator::<H, W>::new(&reference);
+        for _ in 0..30 {
+            reference_generator.generate();
+        }
+
+        for threads in 2..=8 {
+            for strategy in [ChunkStrategy::Rows, ChunkStrategy::Blocks] {
+                let grid = initial.clone();
+                let generator = Generator::<H, W>::new(&grid);
+
+                for _ in 0..30 {
+                    generator.generate_parallel_with(threads, strategy);
+                }
+
+                assert!(
+                    grid == reference,
+                    "{} threads with {:?} diverged",
+                    threads,
+                    strategy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_parallel_strategies() {
+        for strategy in [
+            ChunkStrategy::Rows,
+            ChunkStrategy::Columns,
+            ChunkStrategy::Blocks,
+        ] {
+            let grid = Grid::<24, 30>::new();
+            let reference = Grid::<24, 30>::new();
+            grid.spawn_shape((2, 3), &GLIDER_OFFSETS);
+            grid.spawn_shape((15, 10), &BLINKER_OFFSETS);
+            reference.copy_from(&grid);
+
+            let generator = Generator::<24, 30>::new(&grid);
+            let reference_generator = Generator::<24, 30>::new(&reference);
+
+            for _ in 0..30 {
+                generator.generate_parallel_with(6, strategy);
+                reference_generator.generate();
+                assert_eq!(grid.to_bytes(), reference.to_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_orderings() {
+        use std::sync::atomic::Ordering::Relaxed;
+
+        let grid = Grid::<8, 8>::with_orderings(Relaxed, Relaxed);
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::with_orderings(&grid, Relaxed, Relaxed);
+        generator.generate();
+
+        let expected = Grid::<8, 8>::new();
+        expected.spawn_shape((4, 3), &[(0, 0), (0, 1), (0, 2)]);
+        assert!(grid == expected);
+        assert_eq!(generator.generation(), 1);
+    }
+
+    #[test]
+    fn test_with_orderings_kept() {
+        use std::sync::atomic::Ordering::{Relaxed, SeqCst};
+
+        let grid = Grid::<8, 8>::with_orderings(Relaxed, Relaxed);
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+
+        let generator = Generator::<8, 8>::with_orderings(&grid, SeqCst, SeqCst);
+        generator.generate();
+        generator.generate_parallel(2);
+
+        // The cache holds the last copied states but keeps its own orderings
+        for ((x, y), cell) in grid.enumerate_cells() {
+            assert_eq!(generator.cache.get(x, y).orderings(), (SeqCst, SeqCst));
+            assert_eq!(cell.orderings(), (Relaxed, Relaxed));
+        }
+        assert!(generator.cache.alive(4, 3));
+        assert!(!generator.cache.alive(3, 3));
+    }
+
+    #[test]
+    fn test_with_rule() {
+        // HighLife: B36/S23. A dead cell with 6 neighbors is born
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((2, 2), &[(0, 0), (1, 0), (2, 0), (0, 2), (1, 2), (2, 2)]);
+
+        let generator = Generator::<8, 8>::with_rule(&grid, Rule::new(&[3, 6], &[2, 3]));
+        generator.generate();
+
+        assert!(grid.get(3, 3).alive());
+    }
+
+    #[test]
+    fn test_step_trait_object() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((3, 3), &BLINKER_OFFSETS);
+        let expected = grid.clone();
+
+        let generator: Box<dyn Generate<10, 10> + '_> = Box::new(Generator::<10, 10>::new(&grid));
+
+        for _ in 0..4 {
+            generator.step();
+        }
+
+        // The blinker is back in its original phase after an even number of steps
+        assert!(*generator.grid() == expected);
+    }
+}
@@ -1,6 +1,108 @@
-use std::cell::UnsafeCell;
+use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
+    io::{Read, Write},
+    sync::atomic::Ordering,
+};
+
+use crate::gol::{cell::Cell, coord::Coord, error::GolError, rule::Rule};
+
+use rayon::prelude::*;
+
+const COPY_CHUNK_SIZE: usize = 4096; // Cells copied per rayon task
+const BINARY_MAGIC: &[u8; 4] = b"GOLB"; // Header of the binary format
+const BINARY_VERSION: u16 = 1; // Version of the binary format written by write_binary
+
+// Error returned when a byte buffer does not match the grid size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenError {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+// Implement Display for LenError
+impl std::fmt::Display for LenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected {} bytes for the grid, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for LenError {}
+
+// Relative positions of the 8 neighbors of a cell
+pub const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), // top_left
+    (0, -1),  // top
+    (1, -1),  // top_right
+    (-1, 0),  // left
+    (1, 0),   // right
+    (-1, 1),  // bottom_left
+    (0, 1),   // bottom
+    (1, 1),   // bottom_right
+];
+
+// Circular mean of positions on an axis of length n, as an angle in radians
+pub(crate) fn circular_mean(positions: impl Iterator<Item = usize>, n: usize) -> Option<f64> {
+    let (mut sin, mut cos, mut count) = (0.0, 0.0, 0);
+
+    for position in positions {
+        let angle = position as f64 / n as f64 * std::f64::consts::TAU;
+        sin += angle.sin();
+        cos += angle.cos();
+        count += 1;
+    }
+
+    (count > 0).then(|| sin.atan2(cos))
+}
+
+#[inline]
+// Row-major indices of the distinct neighbors of a cell on a w x h torus,
+// valid up to the returned length. On grids less than 3 cells high or wide several
+// offsets wrap onto the same cell or onto the cell itself. Those are counted once
+// and the cell itself is left out
+pub(crate) fn neighbor_indices(x: isize, y: isize, w: usize, h: usize) -> ([usize; 8], usize) {
+    let index = |x: isize, y: isize| {
+        let wrapped_x = x.rem_euclid(w as isize) as usize;
+        let wrapped_y = y.rem_euclid(h as isize) as usize;
+
+        wrapped_y * w + wrapped_x
+    };
+
+    let mut indices =
+        NEIGHBOR_OFFSETS.map(|(dx, dy)| index(x.wrapping_add(dx), y.wrapping_add(dy)));
 
-use crate::gol::cell::Cell;
+    if h >= 3 && w >= 3 {
+        return (indices, 8);
+    }
+
+    let center = index(x, y);
+    let mut len = 0;
+
+    for i in 0..8 {
+        let index = indices[i];
+        if index != center && !indices[..len].contains(&index) {
+            indices[len] = index;
+            len += 1;
+        }
+    }
+
+    (indices, len)
+}
+
+// Axes a grid can be made symmetric across
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymmetryAxis {
+    // Mirror the left half onto the right half
+    Horizontal,
+    // Mirror the top half onto the bottom half
+    Vertical,
+    // Mirror the top left quadrant onto the other three
+    Both,
+}
 
 // 2D interface to a vector of cells
 // Changes to the contained cells are atomic and a mutable reference
@@ -22,29 +124,333 @@ impl<const H: usize, const W: usize> Grid<H, W> {
         Self { cells }
     }
 
+    // Create a new empty grid whose cells use the given load and store orderings
+    pub fn with_orderings(fetch: Ordering, store: Ordering) -> Self {
+        let mut cells = Vec::with_capacity(H * W);
+
+        for _ in 0..(H * W) {
+            cells.push(Cell::new(fetch, store));
+        }
+
+        Self { cells }
+    }
+
+    // Create a grid from raw state bytes in row-major order
+    // The bytes are stored as is, including their neighbor counts
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LenError> {
+        if bytes.len() != H * W {
+            return Err(LenError {
+                expected: H * W,
+                actual: bytes.len(),
+            });
+        }
+
+        let grid = Self::new();
+
+        for (cell, byte) in grid.cells.iter().zip(bytes) {
+            cell.store(*byte);
+        }
+
+        Ok(grid)
+    }
+
+    // Write the grid in the binary format: the magic bytes "GOLB", a u16 version,
+    // u32 width and u32 height (little endian), then one alive bit per cell in
+    // row-major order, least significant bit first
+    pub fn write_binary(&self, mut w: impl Write) -> std::io::Result<()> {
+        w.write_all(BINARY_MAGIC)?;
+        w.write_all(&BINARY_VERSION.to_le_bytes())?;
+        w.write_all(&(W as u32).to_le_bytes())?;
+        w.write_all(&(H as u32).to_le_bytes())?;
+
+        let mut packed = vec![0u8; (H * W).div_ceil(8)];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell.alive() {
+                packed[i / 8] |= 1 << (i % 8);
+            }
+        }
+
+        w.write_all(&packed)
+    }
+
+    // Read a grid written by write_binary
+    // Neighbor counts are rebuilt from the alive bits
+    pub fn read_binary(mut r: impl Read) -> Result<Self, GolError> {
+        let mut header = [0u8; 14];
+        r.read_exact(&mut header)?;
+
+        if &header[0..4] != BINARY_MAGIC {
+            return Err(GolError::Binary("Missing GOLB magic bytes".to_string()));
+        }
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != BINARY_VERSION {
+            return Err(GolError::Binary(format!(
+                "Unsupported version {}, expected {}",
+                version, BINARY_VERSION
+            )));
+        }
+
+        let width = u32::from_le_bytes(header[6..10].try_into().unwrap()) as usize;
+        let height = u32::from_le_bytes(header[10..14].try_into().unwrap()) as usize;
+        if (width, height) != (W, H) {
+            return Err(GolError::DimensionMismatch {
+                expected: (W, H),
+                actual: (width, height),
+            });
+        }
+
+        let mut packed = vec![0u8; (H * W).div_ceil(8)];
+        r.read_exact(&mut packed)?;
+
+        let grid = Self::new();
+        for i in 0..H * W {
+            if packed[i / 8] >> (i % 8) & 1 == 1 {
+                grid.spawn((i % W) as isize, (i / W) as isize);
+            }
+        }
+
+        Ok(grid)
+    }
+
     #[inline]
-    // Index the grid with 2D coordinates
-    pub fn get(&self, x: isize, y: isize) -> &Cell {
+    // Translate wrapped 2D coordinates to the index of the cell
+    fn index(x: isize, y: isize) -> usize {
         let w = W as isize;
         let h = H as isize;
 
         let wrapped_x = ((x % w + w) % w) as usize;
         let wrapped_y = ((y % h + h) % h) as usize;
 
-        &self.cells[wrapped_y * W + wrapped_x]
+        wrapped_y * W + wrapped_x
+    }
+
+    #[inline]
+    // Index the grid with 2D coordinates
+    pub fn get(&self, x: isize, y: isize) -> &Cell {
+        &self.cells[Self::index(x, y)]
+    }
+
+    #[inline]
+    // Index the grid with a Coord
+    pub fn get_at(&self, coord: Coord) -> &Cell {
+        self.get(coord.x, coord.y)
+    }
+
+    #[inline]
+    // Get a reference to a cell at the given 2D coordinates
+    // Returns None instead of wrapping when the coordinates are outside the grid
+    pub fn get_checked(&self, x: isize, y: isize) -> Option<&Cell> {
+        let in_bounds = (0..W as isize).contains(&x) && (0..H as isize).contains(&y);
+        in_bounds.then(|| &self.cells[y as usize * W + x as usize])
+    }
+
+    #[inline]
+    // Iterate over the cells in row-major order
+    pub fn iter(&self) -> std::slice::Iter<'_, Cell> {
+        self.cells.iter()
+    }
+
+    #[inline]
+    // Iterate over the cells and their 2D coordinates in row-major order
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ((isize, isize), &Cell)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (((i % W) as isize, (i / W) as isize), cell))
+    }
+
+    // Coordinates of every cell in Z-order: (x, y) is visited in the order of the
+    // number whose bits interleave those of x and y. Cells of a square power of two
+    // block are visited before the next block. Other sizes skip codes outside the grid
+    pub fn morton_coordinates(&self) -> impl Iterator<Item = (isize, isize)> {
+        let side = H.max(W).next_power_of_two();
+
+        (0..side * side)
+            .map(|code| (Self::compact_bits(code), Self::compact_bits(code >> 1)))
+            .filter(|(x, y)| *x < W && *y < H)
+            .map(|(x, y)| (x as isize, y as isize))
+    }
+
+    #[inline]
+    // Iterate over the cells in Z-order, see morton_coordinates
+    pub fn iter_morton(&self) -> impl Iterator<Item = &Cell> {
+        self.morton_coordinates().map(|(x, y)| self.get(x, y))
+    }
+
+    // Gather the even bits of a Morton code into a plain number
+    fn compact_bits(code: usize) -> usize {
+        (0..usize::BITS as usize / 2).fold(0, |value, bit| value | ((code >> (2 * bit)) & 1) << bit)
+    }
+
+    #[inline]
+    // Iterate over the cells of a row from left to right. Wraps out-of-range rows
+    pub fn row(&self, y: isize) -> impl Iterator<Item = &Cell> {
+        let start = Self::index(0, y);
+        self.cells[start..start + W].iter()
+    }
+
+    #[inline]
+    // Iterate over the cells of a column from top to bottom. Wraps out-of-range columns
+    pub fn col(&self, x: isize) -> impl Iterator<Item = &Cell> {
+        let start = Self::index(x, 0);
+        self.cells[start..].iter().step_by(W)
+    }
+
+    #[inline]
+    // Snapshot of the raw state byte of every cell in row-major order
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.cells.iter().map(|cell| cell.fetch()).collect()
+    }
+
+    #[inline]
+    // Write the raw state byte of every cell into dst without allocating
+    pub fn as_bytes_into(&self, dst: &mut [u8]) {
+        assert_eq!(dst.len(), H * W, "Destination must have H * W bytes");
+
+        for (state, cell) in dst.iter_mut().zip(self.cells.iter()) {
+            *state = cell.fetch();
+        }
+    }
+
+    // Deterministic FNV-1a hash of the raw state bytes, computed in one scan
+    // Equal grids always hash equally, across runs and platforms
+    pub fn content_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        self.cells.iter().fold(FNV_OFFSET_BASIS, |hash, cell| {
+            (hash ^ cell.fetch() as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    #[inline]
+    // Number of alive cells in the grid
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.alive()).count()
+    }
+
+    #[inline]
+    // Fraction of alive cells in the grid, between 0 and 1
+    pub fn density(&self) -> f64 {
+        self.population() as f64 / (H * W) as f64
+    }
+
+    // Coordinates and (old, new) bytes of every cell that differs from the other grid
+    // self holds the old state and other the new one
+    pub fn diff(&self, other: &Self) -> Vec<(isize, isize, u8, u8)> {
+        self.cells
+            .iter()
+            .zip(other.cells.iter())
+            .enumerate()
+            .filter_map(|(i, (old, new))| {
+                let (old, new) = (old.fetch(), new.fetch());
+                (old != new).then_some(((i % W) as isize, (i / W) as isize, old, new))
+            })
+            .collect()
+    }
+
+    // Offset (dx, dy) such that other shifted by it, wrapping at the edges, equals self
+    // Only the alive bits are compared. Returns the smallest offset in row-major order
+    pub fn equals_translated(&self, other: &Self) -> Option<(isize, isize)> {
+        let alive: Vec<(isize, isize)> = other
+            .enumerate_cells()
+            .filter(|(_, cell)| cell.alive())
+            .map(|(coords, _)| coords)
+            .collect();
+
+        if alive.len() != self.population() {
+            return None;
+        }
+
+        (0..H as isize)
+            .flat_map(|dy| (0..W as isize).map(move |dx| (dx, dy)))
+            .find(|(dx, dy)| alive.iter().all(|(x, y)| self.get(x + dx, y + dy).alive()))
+    }
+
+    // Coordinates, in row-major order, of the cells whose alive bit differs from
+    // the snapshot bytes. Unlike diff, changed neighbor counts are ignored
+    pub fn changed_since(&self, snapshot: &[u8]) -> Vec<(isize, isize)> {
+        assert_eq!(snapshot.len(), H * W, "Snapshot must have H * W bytes");
+
+        self.enumerate_cells()
+            .zip(snapshot)
+            .filter(|((_, cell), state)| cell.alive() != (*state & 1 == 1))
+            .map(|((coords, _), _)| coords)
+            .collect()
+    }
+
+    // Average (x, y) of the live cells, or None if there are none
+    // Each axis uses the circular mean, so a pattern across the wrap boundary
+    // is centered on the boundary instead of the middle of the grid
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let alive: Vec<(usize, usize)> = self
+            .enumerate_cells()
+            .filter(|(_, cell)| cell.alive())
+            .map(|((x, y), _)| (x as usize, y as usize))
+            .collect();
+
+        let position =
+            |angle: f64, n: usize| (angle / std::f64::consts::TAU * n as f64).rem_euclid(n as f64);
+
+        let x = circular_mean(alive.iter().map(|(x, _)| *x), W)?;
+        let y = circular_mean(alive.iter().map(|(_, y)| *y), H)?;
+
+        Some((position(x, W), position(y, H)))
+    }
+
+    // Render the grid as H lines of W characters without borders
+    pub fn render_ascii(&self, alive: char, dead: char) -> String {
+        let mut output = String::with_capacity(H * (W + 1));
+
+        for row in self.cells.chunks(W) {
+            for cell in row {
+                output.push(if cell.alive() { alive } else { dead });
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    // Origins, in row-major order, of every placement where all cells of the shape
+    // are alive. Cells around the shape and neighbor counts are not checked
+    pub fn find_pattern(&self, offsets: &[(isize, isize)]) -> Vec<(isize, isize)> {
+        (0..H as isize)
+            .flat_map(|y| (0..W as isize).map(move |x| (x, y)))
+            .filter(|(x, y)| {
+                offsets
+                    .iter()
+                    .all(|(dx, dy)| self.get(x + dx, y + dy).alive())
+            })
+            .collect()
+    }
+
+    // Number of cells with each neighbor count from 0 to 8
+    pub fn neighbor_histogram(&self) -> [usize; 9] {
+        let mut histogram = [0; 9];
+
+        for cell in self.cells.iter() {
+            histogram[cell.neighbors() as usize] += 1;
+        }
+
+        histogram
     }
 
     #[inline]
     // Spawn a cell at the given 2D coordinates
     // and increment the neighbors of its 8 surrounding cells
+    // Spawning a cell that is already alive does nothing
     pub fn spawn(&self, x: isize, y: isize) {
         let cell = self.get(x, y);
-        let neighbors = self.neighbor_coordinates(x, y);
-        cell.spawn();
+        if !cell.spawn() {
+            return;
+        }
+
+        let (neighbors, len) = Self::neighbor_indices(x, y);
 
-        for (x, y) in neighbors.iter() {
-            let neighbor = self.get(*x, *y);
-            neighbor.add_neighbor();
+        for i in &neighbors[..len] {
+            self.cells[*i].add_neighbor();
         }
     }
 
@@ -53,18 +459,38 @@ impl<const H: usize, const W: usize> Grid<H, W> {
     // and decrement the neighbors of its 8 surrounding cells
     pub fn kill(&self, x: isize, y: isize) {
         let cell = self.get(x, y);
-        let neighbors = self.neighbor_coordinates(x, y);
+        let (neighbors, len) = Self::neighbor_indices(x, y);
         cell.kill();
 
-        for (x, y) in neighbors.iter() {
-            let neighbor = self.get(*x, *y);
-            neighbor.remove_neighbor();
+        for i in &neighbors[..len] {
+            self.cells[*i].remove_neighbor();
+        }
+    }
+
+    // Spawn the cell at the given Coord
+    pub fn spawn_at(&self, coord: Coord) {
+        self.spawn(coord.x, coord.y);
+    }
+
+    // Kill the cell at the given Coord
+    pub fn kill_at(&self, coord: Coord) {
+        self.kill(coord.x, coord.y);
+    }
+
+    // Set the alive state of a cell through spawn and kill so the neighbor
+    // counts around it stay correct. Setting a cell to its current state is a no-op
+    pub fn set_cell_consistent(&self, x: isize, y: isize, alive: bool) {
+        match (alive, self.get(x, y).alive()) {
+            (true, false) => self.spawn(x, y),
+            (false, true) => self.kill(x, y),
+            _ => {}
         }
     }
 
     #[inline]
     // Spawn a shape at the given 2D coordinates
     // the offsets are relative to the start coordinates
+    // Repeated offsets and cells that are already alive are only spawned once
     pub fn spawn_shape(&self, start: (isize, isize), offsets: &[(isize, isize)]) {
         for (dx, dy) in offsets {
             let (x, y) = (start.0 + dx, start.1 + dy);
@@ -72,6 +498,219 @@ impl<const H: usize, const W: usize> Grid<H, W> {
         }
     }
 
+    // Groups of live cells connected through any of their 8 neighbors, wrapping at the edges
+    // Each group starts at its first cell in row-major order and continues in flood fill order
+    pub fn components(&self) -> Vec<Vec<(isize, isize)>> {
+        let mut visited = vec![false; H * W];
+        let mut components = Vec::new();
+
+        for ((x, y), cell) in self.enumerate_cells() {
+            if !cell.alive() || visited[Self::index(x, y)] {
+                continue;
+            }
+
+            visited[Self::index(x, y)] = true;
+            let mut component = vec![(x, y)];
+            let mut next = 0;
+
+            while next < component.len() {
+                let (cx, cy) = component[next];
+                next += 1;
+
+                for (dx, dy) in NEIGHBOR_OFFSETS {
+                    let i = Self::index(cx + dx, cy + dy);
+
+                    if !visited[i] && self.cells[i].alive() {
+                        visited[i] = true;
+                        component.push(((i % W) as isize, (i / W) as isize));
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    // New SH x SW grid holding the window that starts at top_left. The window wraps
+    // around the edges and the alive cells are respawned to rebuild the neighbor counts
+    pub fn subgrid<const SH: usize, const SW: usize>(
+        &self,
+        top_left: (isize, isize),
+    ) -> Grid<SH, SW> {
+        let (left, top) = top_left;
+        let subgrid = Grid::new();
+
+        for y in 0..SH as isize {
+            for x in 0..SW as isize {
+                if self.get(left + x, top + y).alive() {
+                    subgrid.spawn(x, y);
+                }
+            }
+        }
+
+        subgrid
+    }
+
+    // New grid rotated 90 degrees clockwise. The cell at (x, y) moves to (H - 1 - y, x)
+    // The alive cells are respawned so the neighbor counts are rebuilt from scratch
+    pub fn rotated_90(&self) -> Grid<W, H> {
+        let rotated = Grid::<W, H>::new();
+
+        for ((x, y), cell) in self.enumerate_cells() {
+            if cell.alive() {
+                rotated.spawn(H as isize - 1 - y, x);
+            }
+        }
+
+        rotated
+    }
+
+    // New grid mirrored left to right. The cell at (x, y) moves to (W - 1 - x, y)
+    pub fn mirrored_horizontal(&self) -> Self {
+        let mirrored = Self::new();
+
+        for ((x, y), cell) in self.enumerate_cells() {
+            if cell.alive() {
+                mirrored.spawn(W as isize - 1 - x, y);
+            }
+        }
+
+        mirrored
+    }
+
+    // Overwrite the right and/or bottom half with the mirror image of the other half
+    // The middle row or column of an odd sized grid is kept. Cells are spawned
+    // and killed one by one so the neighbor counts stay correct
+    pub fn make_symmetric(&self, axis: SymmetryAxis) {
+        let (w, h) = (W as isize, H as isize);
+
+        if matches!(axis, SymmetryAxis::Horizontal | SymmetryAxis::Both) {
+            for y in 0..h {
+                for x in (w + 1) / 2..w {
+                    self.set_cell_consistent(x, y, self.get(w - 1 - x, y).alive());
+                }
+            }
+        }
+
+        if matches!(axis, SymmetryAxis::Vertical | SymmetryAxis::Both) {
+            for y in (h + 1) / 2..h {
+                for x in 0..w {
+                    self.set_cell_consistent(x, y, self.get(x, h - 1 - y).alive());
+                }
+            }
+        }
+    }
+
+    // Swap the cells at (x, y) and (y, x) of a square grid and fix the neighbor counts
+    pub fn transpose_in_place(&self) {
+        assert_eq!(H, W, "Only square grids can be transposed in place");
+
+        for y in 0..H {
+            for x in y + 1..W {
+                let upper = &self.cells[y * W + x];
+                let lower = &self.cells[x * W + y];
+
+                lower.store(upper.exchange(lower.fetch()));
+            }
+        }
+
+        self.recompute_neighbors();
+    }
+
+    // Kill every cell and reset all neighbor counts and ages
+    pub fn clear(&self) {
+        for cell in self.cells.iter() {
+            cell.store(0);
+        }
+    }
+
+    // Clear the grid and spawn the shape at the origin
+    pub fn set_pattern(&self, offsets: &[(isize, isize)], origin: (isize, isize)) {
+        self.clear();
+        self.spawn_shape(origin, offsets);
+    }
+
+    // Spawn many cells at once. The alive bits are set first and the
+    // neighbor counts of all affected cells are then updated with a single
+    // atomic operation each. Cells that are already alive are skipped
+    pub fn spawn_batch(&self, coords: &[(isize, isize)]) {
+        let mut deltas: HashMap<usize, u8> = HashMap::new();
+
+        for (x, y) in coords {
+            if !self.get(*x, *y).spawn() {
+                continue;
+            }
+
+            let (neighbors, len) = Self::neighbor_indices(*x, *y);
+
+            for i in &neighbors[..len] {
+                *deltas.entry(*i).or_insert(0) += 1;
+            }
+        }
+
+        for (idx, delta) in deltas {
+            self.cells[idx].add_neighbors(delta);
+        }
+    }
+
+    // Whether the cell at the given 2D coordinates is alive in the next generation under B3/S23
+    // Reads the stored alive bit and neighbor count without changing anything
+    pub fn next_state(&self, x: isize, y: isize) -> bool {
+        let cell = self.get(x, y);
+
+        match cell.alive() {
+            true => Rule::conway().survives(cell.neighbors()),
+            false => Rule::conway().born(cell.neighbors()),
+        }
+    }
+
+    // Bitmask of the alive neighbors of the cell at the given 2D coordinates
+    // Bit i is set if the neighbor at NEIGHBOR_OFFSETS[i] is alive
+    pub fn live_neighbor_mask(&self, x: isize, y: isize) -> u8 {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .enumerate()
+            .filter(|(_, (dx, dy))| self.get(x + dx, y + dy).alive())
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    // Count the alive cells around the given 2D coordinates from scratch
+    fn count_live_neighbors(&self, x: isize, y: isize) -> u8 {
+        let (neighbors, len) = Self::neighbor_indices(x, y);
+
+        neighbors[..len]
+            .iter()
+            .filter(|i| self.cells[**i].alive())
+            .count() as u8
+    }
+
+    // Recount the neighbors of every cell from the alive bits
+    // and store the corrected counts. Fixes grids loaded from untrusted bytes
+    pub fn recompute_neighbors(&self) {
+        for y in 0..H as isize {
+            for x in 0..W as isize {
+                let count = self.count_live_neighbors(x, y);
+                let cell = self.get(x, y);
+                cell.store((cell.fetch() & !0b0001_1110) | (count << 1));
+            }
+        }
+    }
+
+    // Returns true if every cell's neighbor count matches a fresh recount
+    pub fn verify_neighbors(&self) -> bool {
+        self.neighbor_mismatch().is_none()
+    }
+
+    // Coordinates of the first cell, in row-major order, whose stored
+    // neighbor count differs from a fresh recount
+    pub fn neighbor_mismatch(&self) -> Option<(isize, isize)> {
+        (0..H as isize)
+            .flat_map(|y| (0..W as isize).map(move |x| (x, y)))
+            .find(|(x, y)| self.get(*x, *y).neighbors() != self.count_live_neighbors(*x, *y))
+    }
+
     //TODO: Explore optimizations for this
     #[inline]
     // Copy the state of the grid to another grid
@@ -85,6 +724,28 @@ impl<const H: usize, const W: usize> Grid<H, W> {
         }
     }
 
+    // Copy the state of another grid with rayon, one chunk of cells per task
+    pub fn copy_from_parallel(&self, other: &Self) {
+        self.cells
+            .par_chunks(COPY_CHUNK_SIZE)
+            .zip(other.cells.par_chunks(COPY_CHUNK_SIZE))
+            .for_each(|(cells, others)| {
+                for (cell, other) in cells.iter().zip(others) {
+                    cell.copy_relaxed(other);
+                }
+            });
+    }
+
+    // Copy the cells of a grid with the same number of cells in flat row-major order
+    // Neighbor counts are copied as is, call recompute_neighbors after reshaping a pattern
+    pub fn copy_flat_from<const H2: usize, const W2: usize>(&self, other: &Grid<H2, W2>) {
+        assert_eq!(H * W, H2 * W2, "Grids must have the same number of cells");
+
+        for (cell, other_cell) in self.cells.iter().zip(other.iter()) {
+            cell.store(other_cell.fetch());
+        }
+    }
+
     #[inline]
     // Unsafe copy the state of the grid to another grid
     // SAFETY: The grids must have the same size. The function
@@ -105,19 +766,35 @@ impl<const H: usize, const W: usize> Grid<H, W> {
         );
     }
 
+    #[inline]
+    // Indices of the distinct neighbors of a cell, see the free neighbor_indices
+    fn neighbor_indices(x: isize, y: isize) -> ([usize; 8], usize) {
+        neighbor_indices(x, y, W, H)
+    }
+
     // Utility function to get the wrapped 2D coordinates
     #[inline]
     pub fn neighbor_coordinates(&self, x: isize, y: isize) -> [(isize, isize); 8] {
-        [
-            (x.wrapping_sub(1), y.wrapping_sub(1)), // top_left
-            (x, y.wrapping_sub(1)),                 // top
-            (x.wrapping_add(1), y.wrapping_sub(1)), // top_right
-            (x.wrapping_sub(1), y),                 // left
-            (x.wrapping_add(1), y),                 // right
-            (x.wrapping_sub(1), y.wrapping_add(1)), // bottom_left
-            (x, y.wrapping_add(1)),                 // bottom
-            (x.wrapping_add(1), y.wrapping_add(1)), // bottom_right
-        ]
+        NEIGHBOR_OFFSETS.map(|(dx, dy)| (x.wrapping_add(dx), y.wrapping_add(dy)))
+    }
+}
+
+// Implement Clone for Grid: Deep copies every cell
+impl<const H: usize, const W: usize> Clone for Grid<H, W> {
+    fn clone(&self) -> Self {
+        Self {
+            cells: self.cells.clone(),
+        }
+    }
+}
+
+// Implement PartialEq for Grid: Grids are equal if every cell has the same state
+impl<const H: usize, const W: usize> PartialEq for Grid<H, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells
+            .iter()
+            .zip(other.cells.iter())
+            .all(|(cell, other_cell)| cell.fetch() == other_cell.fetch())
     }
 }
 
@@ -162,6 +839,7 @@ impl<const H: usize, const W: usize> std::fmt::Display for Grid<H, W> {
 #[cfg(test)]
 mod tests {
     use crate::gol::*;
+    use grid::LenError;
     use utils::*;
 
     use std::{sync::Arc, thread};
@@ -171,6 +849,8 @@ mod tests {
 
         pub const BLOCK_SHAPE_OFFSETS: [(isize, isize); 4] = [(0, 0), (1, 0), (0, 1), (1, 1)];
 
+        pub use crate::gol::checked::GOSPER_GUN_OFFSETS;
+
         // Set the cell at the given index to dead and 0 neighbors
         pub fn set_0b0000_0000<const H: usize, const W: usize>(grid: &mut Grid<H, W>, idx: usize) {
             let cell = &mut grid.cells[idx];
@@ -437,6 +1117,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_copy_from_parallel() {
+        const H: usize = 500;
+        const W: usize = 500;
+
+        let other = Grid::<H, W>::new();
+        randomize_grid_seeded(&other, 3, 0.5);
+
+        let serial = Grid::<H, W>::new();
+        serial.copy_from(&other);
+
+        let parallel = Grid::<H, W>::new();
+        parallel.copy_from_parallel(&other);
+
+        assert!(parallel == serial);
+        assert!(parallel == other);
+    }
+
+    #[test]
+    fn test_copy_flat_from() {
+        let other = Grid::<2, 8>::new();
+        other.spawn_shape((1, 0), &BLOCK_SHAPE_OFFSETS);
+        other.spawn(6, 1);
+
+        let grid = Grid::<4, 4>::new();
+        grid.copy_flat_from(&other);
+        assert_eq!(grid.to_bytes(), other.to_bytes());
+
+        grid.recompute_neighbors();
+        assert!(grid.verify_neighbors());
+        assert_eq!(grid.population(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Grids must have the same number of cells")]
+    fn test_copy_flat_from_size_mismatch() {
+        Grid::<4, 4>::new().copy_flat_from(&Grid::<3, 5>::new());
+    }
+
     #[test]
     fn test_raw_unsafe_copy() {
         use std::cell::UnsafeCell;
@@ -505,6 +1224,698 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clone() {
+        let grid = Grid::<4, 4>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+
+        let clone = grid.clone();
+        assert!(clone == grid);
+        assert_eq!(clone.to_bytes(), grid.to_bytes());
+
+        // Mutating the clone leaves the original untouched
+        clone.kill(1, 1);
+        assert!(clone != grid);
+        assert!(grid.get(1, 1).alive());
+        assert_eq!(grid.get(0, 0).neighbors(), 1);
+        assert_eq!(clone.get(0, 0).neighbors(), 0);
+    }
+
+    #[test]
+    fn test_as_bytes_into() {
+        let grid = Grid::<6, 5>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+        grid.get(4, 4).set_age(3);
+
+        let mut buffer = vec![0xFF; 6 * 5];
+        grid.as_bytes_into(&mut buffer);
+        assert_eq!(buffer, grid.to_bytes());
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let grid = Grid::<4, 4>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+
+        let restored = Grid::<4, 4>::from_bytes(&grid.to_bytes()).unwrap();
+        assert!(restored == grid);
+
+        let error = Grid::<4, 4>::from_bytes(&[0; 15]).err().unwrap();
+        assert_eq!(
+            error,
+            LenError {
+                expected: 16,
+                actual: 15
+            }
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let grid = Grid::<10, 13>::new();
+        grid.spawn_shape((0, 0), &GOSPER_GUN_OFFSETS[..20]);
+
+        let mut bytes = Vec::new();
+        grid.write_binary(std::io::Cursor::new(&mut bytes)).unwrap();
+        assert_eq!(&bytes[0..4], b"GOLB");
+        assert_eq!(bytes.len(), 14 + (10 * 13 + 7) / 8);
+
+        let loaded = Grid::<10, 13>::read_binary(bytes.as_slice()).unwrap();
+        assert!(loaded == grid);
+        assert!(loaded.verify_neighbors());
+
+        // Dimensions are checked against the target type
+        assert!(matches!(
+            Grid::<13, 10>::read_binary(bytes.as_slice()),
+            Err(GolError::DimensionMismatch {
+                expected: (10, 13),
+                actual: (13, 10)
+            })
+        ));
+
+        // Unknown versions are rejected
+        let mut future = bytes.clone();
+        future[4..6].copy_from_slice(&2u16.to_le_bytes());
+        assert!(matches!(
+            Grid::<10, 13>::read_binary(future.as_slice()),
+            Err(GolError::Binary(_))
+        ));
+
+        // Truncated data surfaces as an I/O error
+        assert!(matches!(
+            Grid::<10, 13>::read_binary(&bytes[..bytes.len() - 1]),
+            Err(GolError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_set_cell_consistent() {
+        let grid = Grid::<6, 6>::new();
+        for (dx, dy) in BLOCK_SHAPE_OFFSETS {
+            grid.set_cell_consistent(2 + dx, 2 + dy, true);
+        }
+
+        // Setting a cell to its current state changes nothing
+        grid.set_cell_consistent(2, 2, true);
+        grid.set_cell_consistent(0, 0, false);
+
+        assert!(grid.verify_neighbors());
+        assert_eq!(grid.get(2, 2).neighbors(), 3);
+        assert_eq!(grid.get(1, 1).neighbors(), 1);
+        assert_eq!(grid.get(1, 2).neighbors(), 2);
+
+        // Raw stores set the alive bits but leave every neighbor count at zero
+        let raw = Grid::<6, 6>::new();
+        for (dx, dy) in BLOCK_SHAPE_OFFSETS {
+            raw.get(2 + dx, 2 + dy).store(0b0000_0001);
+        }
+
+        assert_eq!(raw.population(), 4);
+        assert_eq!(raw.get(2, 2).neighbors(), 0);
+        assert!(!raw.verify_neighbors());
+
+        grid.set_cell_consistent(3, 3, false);
+        assert_eq!(grid.population(), 3);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_components() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+        grid.spawn_shape((5, 6), &BLOCK_SHAPE_OFFSETS);
+
+        let components = grid.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], [(1, 1), (2, 1), (1, 2), (2, 2)]);
+        assert_eq!(components[1].len(), 4);
+        assert!(components[1].contains(&(6, 7)));
+
+        // Diagonal neighbors connect and a block split by the seam is a single component
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((9, 9), &BLOCK_SHAPE_OFFSETS);
+        grid.spawn(8, 8);
+
+        let mut components = grid.components();
+        assert_eq!(components.len(), 1);
+
+        components[0].sort();
+        assert_eq!(components[0], [(0, 0), (0, 9), (8, 8), (9, 0), (9, 9)]);
+
+        assert!(Grid::<4, 4>::new().components().is_empty());
+    }
+
+    #[test]
+    fn test_subgrid() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((4, 5), &glider);
+        grid.spawn(0, 0);
+
+        let subgrid = grid.subgrid::<3, 3>((4, 5));
+        assert_eq!(subgrid.render_ascii('#', '.'), ".#.\n..#\n###\n");
+        assert!(subgrid.verify_neighbors());
+
+        // The window wraps around the edges of the grid
+        let wrapped = Grid::<10, 10>::new();
+        wrapped.spawn_shape((9, 9), &glider);
+        assert!(wrapped.subgrid::<3, 3>((9, 9)) == subgrid);
+    }
+
+    #[test]
+    fn test_recompute_neighbors() {
+        let grid = Grid::<4, 4>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+        assert!(grid.verify_neighbors());
+
+        // Alive bits of a block with every neighbor count wrong
+        let mut bytes = vec![0b0000_1110; 16];
+        for (x, y) in BLOCK_SHAPE_OFFSETS {
+            bytes[((y + 1) * 4 + x + 1) as usize] = 0b0000_0001;
+        }
+
+        let corrupted = Grid::<4, 4>::from_bytes(&bytes).unwrap();
+        assert!(!corrupted.verify_neighbors());
+
+        corrupted.recompute_neighbors();
+        assert!(corrupted.verify_neighbors());
+        assert!(corrupted == grid);
+    }
+
+    #[test]
+    fn test_spawn_batch() {
+        let grid = Grid::<16, 40>::new();
+        let batched = Grid::<16, 40>::new();
+
+        grid.spawn_shape((2, 3), &GOSPER_GUN_OFFSETS);
+
+        let coords: Vec<(isize, isize)> = GOSPER_GUN_OFFSETS
+            .iter()
+            .map(|(x, y)| (x + 2, y + 3))
+            .collect();
+        batched.spawn_batch(&coords);
+
+        assert!(batched == grid);
+        assert!(batched.verify_neighbors());
+
+        // Spawning an alive cell again is a no-op
+        batched.spawn_batch(&coords[..4]);
+        assert!(batched == grid);
+    }
+
+    #[test]
+    fn test_spawn_batch_rectangle() {
+        // 128 adjacent cells: a 16x8 rectangle
+        let coords: Vec<(isize, isize)> = (0..8)
+            .flat_map(|y| (0..16).map(move |x| (x + 8, y + 8)))
+            .collect();
+
+        let grid = Grid::<32, 32>::new();
+        for (x, y) in &coords {
+            grid.spawn(*x, *y);
+        }
+
+        let batched = Grid::<32, 32>::new();
+        batched.spawn_batch(&coords);
+
+        assert!(batched == grid);
+        assert!(batched.verify_neighbors());
+    }
+
+    #[test]
+    fn test_rotated_90() {
+        // F-pentomino
+        let grid = Grid::<5, 7>::new();
+        grid.spawn_shape((1, 1), &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]);
+
+        let rotated = grid.rotated_90();
+        let expected = Grid::<7, 5>::new();
+        for (x, y) in [(3, 2), (3, 3), (2, 1), (2, 2), (1, 2)] {
+            expected.spawn(x, y);
+        }
+
+        assert!(rotated == expected);
+        assert!(rotated.verify_neighbors());
+        assert!(rotated.rotated_90().rotated_90().rotated_90() == grid);
+    }
+
+    #[test]
+    fn test_make_symmetric() {
+        let grid = Grid::<6, 9>::new();
+        randomize_grid_seeded(&grid, 1877, 0.5);
+
+        grid.make_symmetric(SymmetryAxis::Horizontal);
+        assert!(grid.mirrored_horizontal() == grid);
+        assert!(grid.verify_neighbors());
+
+        for y in 0..6 {
+            for x in 0..4 {
+                assert_eq!(grid.get(x, y).alive(), grid.get(8 - x, y).alive());
+            }
+        }
+
+        let grid = Grid::<7, 8>::new();
+        grid.spawn_shape((0, 0), &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        grid.spawn_shape((1, 5), &BLOCK_SHAPE_OFFSETS);
+        grid.make_symmetric(SymmetryAxis::Vertical);
+
+        // The bottom half is replaced, so the block is gone
+        assert_eq!(
+            grid.render_ascii('#', '.'),
+            ".#......\n..#.....\n###.....\n........\n###.....\n..#.....\n.#......\n"
+        );
+        assert!(grid.verify_neighbors());
+
+        let grid = Grid::<8, 8>::new();
+        grid.spawn_shape((1, 1), &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        grid.make_symmetric(SymmetryAxis::Both);
+
+        assert_eq!(grid.population(), 20);
+        assert!(grid.mirrored_horizontal() == grid);
+        assert!(grid.rotated_90().rotated_90().mirrored_horizontal() == grid);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_mirrored_horizontal() {
+        let grid = Grid::<5, 7>::new();
+        grid.spawn_shape((1, 1), &[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]);
+
+        let mirrored = grid.mirrored_horizontal();
+        let expected = Grid::<5, 7>::new();
+        for (x, y) in [(4, 1), (3, 1), (5, 2), (4, 2), (4, 3)] {
+            expected.spawn(x, y);
+        }
+
+        assert!(mirrored == expected);
+        assert!(mirrored.verify_neighbors());
+        assert!(mirrored.mirrored_horizontal() == grid);
+    }
+
+    #[test]
+    fn test_transpose_in_place() {
+        let grid = Grid::<4, 4>::new();
+        grid.spawn_shape((0, 0), &[(1, 0), (2, 0), (3, 0), (0, 2), (3, 3)]);
+
+        let expected = Grid::<4, 4>::new();
+        expected.spawn_shape((0, 0), &[(0, 1), (0, 2), (0, 3), (2, 0), (3, 3)]);
+
+        grid.transpose_in_place();
+        assert!(grid == expected);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only square grids can be transposed in place")]
+    fn test_transpose_in_place_non_square() {
+        Grid::<4, 5>::new().transpose_in_place();
+    }
+
+    #[test]
+    fn test_spawn_shape_duplicates() {
+        let grid = Grid::<6, 6>::new();
+        grid.spawn_shape(
+            (1, 1),
+            &[(0, 0), (1, 0), (0, 0), (0, 1), (1, 1), (1, 0), (1, 1)],
+        );
+
+        let expected = Grid::<6, 6>::new();
+        expected.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+
+        assert!(grid == expected);
+        assert!(grid.verify_neighbors());
+
+        // Spawning over a live block leaves it untouched
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+        assert!(grid == expected);
+        assert_eq!(grid.get(1, 1).neighbors(), 3);
+    }
+
+    #[test]
+    fn test_set_pattern() {
+        let grid = Grid::<10, 10>::new();
+        grid.spawn_shape((1, 1), &[(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)]);
+
+        let generator = Generator::<10, 10>::new(&grid);
+        for _ in 0..7 {
+            generator.generate();
+        }
+
+        grid.set_pattern(&BLOCK_SHAPE_OFFSETS, (6, 2));
+
+        let expected = Grid::<10, 10>::new();
+        expected.spawn_shape((6, 2), &BLOCK_SHAPE_OFFSETS);
+
+        assert!(grid == expected);
+        assert_eq!(grid.population(), 4);
+        assert!(grid.verify_neighbors());
+
+        grid.clear();
+        assert!(grid.to_bytes().iter().all(|state| *state == 0));
+    }
+
+    #[test]
+    fn test_enumerate_cells() {
+        let grid = Grid::<2, 3>::new();
+        grid.spawn(2, 1);
+
+        let coordinates: Vec<(isize, isize)> = grid.enumerate_cells().map(|(xy, _)| xy).collect();
+        assert_eq!(
+            coordinates,
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+
+        for ((x, y), cell) in grid.enumerate_cells() {
+            assert!(std::ptr::eq(cell, grid.get(x, y)));
+        }
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let grid = Grid::<3, 5>::new();
+        grid.spawn(4, 2);
+
+        assert!(grid.get_checked(4, 2).unwrap().alive());
+        assert!(std::ptr::eq(
+            grid.get_checked(0, 0).unwrap(),
+            grid.get(0, 0)
+        ));
+
+        for (x, y) in [(-1, 0), (0, -1), (5, 0), (0, 3), (5, 3)] {
+            assert!(grid.get_checked(x, y).is_none());
+        }
+    }
+
+    #[test]
+    fn test_neighbor_coordinates() {
+        let grid = Grid::<4, 4>::new();
+
+        let wrapped = grid
+            .neighbor_coordinates(0, 0)
+            .map(|(x, y)| (x.rem_euclid(4), y.rem_euclid(4)));
+
+        assert_eq!(
+            wrapped,
+            [
+                (3, 3),
+                (0, 3),
+                (1, 3),
+                (3, 0),
+                (1, 0),
+                (3, 1),
+                (0, 1),
+                (1, 1)
+            ]
+        );
+
+        for ((x, y), (wx, wy)) in grid.neighbor_coordinates(0, 0).iter().zip(wrapped) {
+            assert!(std::ptr::eq(grid.get(*x, *y), grid.get(wx, wy)));
+        }
+    }
+
+    #[test]
+    fn test_next_state() {
+        let grid = Grid::<8, 8>::new();
+
+        // Dead with 3 neighbors is born, dead with 2 or 4 stays dead
+        grid.spawn_batch(&[(0, 0), (1, 0), (2, 0)]);
+        assert!(grid.next_state(1, 1));
+        assert!(!grid.next_state(0, 1));
+        grid.spawn(1, 2);
+        assert!(!grid.next_state(1, 1));
+
+        let grid = Grid::<8, 8>::new();
+        grid.spawn(4, 4);
+
+        // Alive with 1 dies, with 2 or 3 survives and with 4 dies
+        grid.spawn(3, 3);
+        assert!(!grid.next_state(4, 4));
+        grid.spawn(5, 5);
+        assert!(grid.next_state(4, 4));
+        grid.spawn(3, 5);
+        assert!(grid.next_state(4, 4));
+        grid.spawn(5, 3);
+        assert!(!grid.next_state(4, 4));
+
+        // Nothing was changed by asking
+        assert_eq!(grid.population(), 5);
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_degenerate_grids() {
+        // On a single row the cells above and below a cell are the cell itself
+        let grid = Grid::<1, 5>::new();
+        grid.spawn(2, 0);
+
+        assert_eq!(grid.get(1, 0).neighbors(), 1);
+        assert_eq!(grid.get(3, 0).neighbors(), 1);
+        assert_eq!(grid.get(2, 0).neighbors(), 0);
+        assert_eq!(grid.get(0, 0).neighbors(), 0);
+        assert!(grid.verify_neighbors());
+
+        grid.kill(2, 0);
+        assert!(grid.iter().all(|cell| *cell == 0));
+
+        // On two columns the left and right neighbors are the same cell
+        let grid = Grid::<5, 2>::new();
+        grid.spawn_batch(&[(0, 1), (0, 2), (0, 3)]);
+
+        assert_eq!(grid.get(1, 2).neighbors(), 3);
+        assert_eq!(grid.get(0, 2).neighbors(), 2);
+        assert!(grid.verify_neighbors());
+
+        // A 1x1 grid has no neighbors at all
+        let grid = Grid::<1, 1>::new();
+        grid.spawn(0, 0);
+        assert_eq!(grid.get(0, 0).fetch(), 0b0000_0001);
+    }
+
+    #[test]
+    fn test_live_neighbor_mask() {
+        let grid = Grid::<5, 5>::new();
+
+        /* L-shape, corner at (1, 2)
+           [0][1][0][0][0]
+           [0][1][0][0][0]
+           [0][1][1][0][0]
+           [0][0][0][0][0]
+           [0][0][0][0][0]
+        */
+        grid.spawn_shape((1, 0), &[(0, 0), (0, 1), (0, 2), (1, 2)]);
+
+        // Only the top and right neighbors are alive
+        assert_eq!(grid.live_neighbor_mask(1, 2), 0b0001_0010);
+
+        // (2, 1) sees top_left, left, bottom_left and bottom
+        assert_eq!(grid.live_neighbor_mask(2, 1), 0b0110_1001);
+
+        // Wrapped: (1, 4) sees the top of the L below it as bottom
+        assert_eq!(grid.live_neighbor_mask(1, 4), 0b0100_0000);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let grid = Grid::<8, 8>::new();
+        let other = Grid::<8, 8>::new();
+        assert_eq!(grid.content_hash(), other.content_hash());
+
+        grid.spawn_shape((2, 2), &BLOCK_SHAPE_OFFSETS);
+        other.spawn_shape((2, 2), &BLOCK_SHAPE_OFFSETS);
+        assert_eq!(grid.content_hash(), other.content_hash());
+
+        // Toggling a single cell changes the hash
+        other.kill(2, 2);
+        assert_ne!(grid.content_hash(), other.content_hash());
+        other.spawn(2, 2);
+        assert_eq!(grid.content_hash(), other.content_hash());
+
+        // FNV-1a of 64 zero bytes, fixed across runs
+        assert_eq!(Grid::<8, 8>::new().content_hash(), 0xb9b2_3f3a_46fd_0825);
+    }
+
+    #[test]
+    fn test_row_col() {
+        let grid = Grid::<3, 3>::new();
+
+        /* Known first row and last column
+           [1][0][1]
+           [0][0][1]
+           [0][0][0]
+        */
+        grid.spawn_shape((0, 0), &[(0, 0), (2, 0), (2, 1)]);
+
+        let alive = |cells: Vec<&Cell>| cells.iter().map(|cell| cell.alive()).collect::<Vec<_>>();
+
+        assert_eq!(alive(grid.row(0).collect()), [true, false, true]);
+        assert_eq!(alive(grid.row(1).collect()), [false, false, true]);
+        assert_eq!(alive(grid.row(3).collect()), [true, false, true]);
+        assert_eq!(alive(grid.row(-1).collect()), [false, false, false]);
+
+        assert_eq!(alive(grid.col(2).collect()), [true, true, false]);
+        assert_eq!(alive(grid.col(-1).collect()), [true, true, false]);
+        assert_eq!(alive(grid.col(0).collect()), [true, false, false]);
+    }
+
+    #[test]
+    fn test_density() {
+        let mut grid = Grid::<4, 4>::new();
+        assert_eq!(grid.density(), 0.0);
+
+        grid.spawn_shape((0, 0), &BLOCK_SHAPE_OFFSETS);
+        assert_eq!(grid.density(), 0.25);
+
+        for i in 0..grid.cells.len() {
+            set_0b0001_0001(&mut grid, i);
+        }
+        assert_eq!(grid.density(), 1.0);
+    }
+
+    #[test]
+    fn test_diff() {
+        let grid = Grid::<4, 6>::new();
+        grid.spawn_shape((1, 1), &BLOCK_SHAPE_OFFSETS);
+
+        let other = grid.clone();
+        assert!(grid.diff(&other).is_empty());
+
+        other.get(3, 2).spawn();
+        assert_eq!(grid.diff(&other), [(3, 2, 0b0000_0100, 0b0000_0101)]);
+    }
+
+    #[test]
+    fn test_equals_translated() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        let grid = Grid::<10, 12>::new();
+        grid.spawn_shape((10, 8), &glider);
+        let initial = grid.clone();
+
+        let generator = Generator::<10, 12>::new(&grid);
+        for _ in 0..4 {
+            generator.generate();
+        }
+
+        // One period moves the glider one cell down and right, across the seam
+        assert_eq!(grid.equals_translated(&initial), Some((1, 1)));
+        assert_eq!(initial.equals_translated(&grid), Some((11, 9)));
+        assert_eq!(grid.equals_translated(&grid), Some((0, 0)));
+
+        generator.generate();
+        assert_eq!(grid.equals_translated(&initial), None);
+
+        assert_eq!(
+            Grid::<4, 4>::new().equals_translated(&Grid::new()),
+            Some((0, 0))
+        );
+    }
+
+    #[test]
+    fn test_iter_morton() {
+        // Tag every cell with its row-major index
+        let grid = Grid::<4, 4>::new();
+        for (i, cell) in grid.iter().enumerate() {
+            cell.store(i as u8);
+        }
+
+        let indices: Vec<u8> = grid.iter_morton().map(Cell::fetch).collect();
+
+        assert_eq!(
+            indices,
+            [0, 1, 4, 5, 2, 3, 6, 7, 8, 9, 12, 13, 10, 11, 14, 15]
+        );
+
+        // Codes outside a grid that is not a square power of two are skipped
+        let grid = Grid::<2, 3>::new();
+        let coordinates: Vec<(isize, isize)> = grid.morton_coordinates().collect();
+        assert_eq!(
+            coordinates,
+            [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_centroid() {
+        let close = |(x, y): (f64, f64), (ex, ey): (f64, f64)| {
+            (x - ex).abs() < 1e-9 && (y - ey).abs() < 1e-9
+        };
+
+        let grid = Grid::<10, 12>::new();
+        assert_eq!(grid.centroid(), None);
+
+        grid.spawn_shape((1, 2), &BLOCK_SHAPE_OFFSETS);
+        assert!(close(grid.centroid().unwrap(), (1.5, 2.5)));
+
+        // A block split by both seams is centered on the corner, not the middle
+        let grid = Grid::<10, 12>::new();
+        grid.spawn_shape((11, 9), &BLOCK_SHAPE_OFFSETS);
+        assert!(close(grid.centroid().unwrap(), (11.5, 9.5)));
+
+        let grid = Grid::<10, 12>::new();
+        grid.spawn_shape((-1, 0), &BLOCK_SHAPE_OFFSETS);
+        assert!(close(grid.centroid().unwrap(), (11.5, 0.5)));
+    }
+
+    #[test]
+    fn test_changed_since() {
+        let grid = Grid::<6, 6>::new();
+        grid.spawn_batch(&[(1, 2), (2, 2), (3, 2)]);
+        let snapshot = grid.to_bytes();
+
+        Generator::<6, 6>::new(&grid).generate();
+
+        assert_eq!(
+            grid.changed_since(&snapshot),
+            [(2, 1), (1, 2), (3, 2), (2, 3)]
+        );
+        assert!(grid.changed_since(&grid.to_bytes()).is_empty());
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let grid = Grid::<5, 5>::new();
+        grid.spawn_shape((1, 2), &[(0, 0), (1, 0), (2, 0)]);
+
+        let generator = Generator::<5, 5>::new(&grid);
+
+        let horizontal = ".....\n.....\n.###.\n.....\n.....\n";
+        let vertical = ".....\n..#..\n..#..\n..#..\n.....\n";
+
+        assert_eq!(grid.render_ascii('#', '.'), horizontal);
+        generator.generate();
+        assert_eq!(grid.render_ascii('#', '.'), vertical);
+        generator.generate();
+        assert_eq!(grid.render_ascii('#', '.'), horizontal);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        const GLIDER_OFFSETS: [(isize, isize); 5] = [(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)];
+
+        let grid = Grid::<12, 12>::new();
+        grid.spawn_shape((1, 2), &GLIDER_OFFSETS);
+        grid.spawn_shape((10, 7), &GLIDER_OFFSETS);
+
+        assert_eq!(grid.find_pattern(&GLIDER_OFFSETS), [(1, 2), (10, 7)]);
+        assert!(grid.find_pattern(&BLOCK_SHAPE_OFFSETS).is_empty());
+    }
+
+    #[test]
+    fn test_neighbor_histogram() {
+        let grid = Grid::<5, 6>::new();
+
+        for y in 0..5 {
+            for x in 0..6 {
+                grid.spawn(x, y);
+            }
+        }
+
+        let mut expected = [0; 9];
+        expected[8] = 5 * 6;
+        assert_eq!(grid.neighbor_histogram(), expected);
+    }
+
     #[test]
     fn test_threading() {
         let grid = Grid::<4, 4>::new();
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::gol::rule::Rule;
+
+// Unbounded grid storing only live cells and the dead cells next to them
+// Each entry uses the same layout as Cell: bit 0 is alive, bits 1-4 the neighbor count
+pub struct InfiniteGrid {
+    cells: HashMap<(i64, i64), u8>,
+    rule: Rule,
+}
+
+// Implement InfiniteGrid
+impl InfiniteGrid {
+    pub fn new() -> Self {
+        Self::with_rule(Rule::conway())
+    }
+
+    // Rules that give birth to cells without neighbors would fill the plane
+    pub fn with_rule(rule: Rule) -> Self {
+        assert!(
+            !rule.born(0),
+            "Rules with B0 cannot run on an infinite grid"
+        );
+
+        Self {
+            cells: HashMap::new(),
+            rule,
+        }
+    }
+
+    #[inline]
+    pub fn alive(&self, x: i64, y: i64) -> bool {
+        self.cells.get(&(x, y)).is_some_and(|state| state & 1 != 0)
+    }
+
+    #[inline]
+    pub fn neighbors(&self, x: i64, y: i64) -> u8 {
+        self.cells
+            .get(&(x, y))
+            .map_or(0, |state| (state >> 1) & 0b1111)
+    }
+
+    #[inline]
+    pub fn population(&self) -> usize {
+        self.cells.values().filter(|state| *state & 1 != 0).count()
+    }
+
+    // Coordinates of the live cells sorted by row, then column
+    pub fn live_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells: Vec<(i64, i64)> = self
+            .cells
+            .iter()
+            .filter(|(_, state)| *state & 1 != 0)
+            .map(|(position, _)| *position)
+            .collect();
+
+        cells.sort_by_key(|(x, y)| (*y, *x));
+        cells
+    }
+
+    // Spawn a cell and increment the neighbors of its 8 surrounding cells
+    // Spawning a live cell does nothing
+    pub fn spawn(&mut self, x: i64, y: i64) {
+        let state = self.cells.entry((x, y)).or_insert(0);
+        if *state & 1 != 0 {
+            return;
+        }
+        *state |= 1;
+
+        for position in Self::neighbor_coordinates(x, y) {
+            *self.cells.entry(position).or_insert(0) += 0b10;
+        }
+    }
+
+    // Kill a cell and decrement the neighbors of its 8 surrounding cells
+    // Entries left dead without neighbors are dropped. Killing a dead cell does nothing
+    pub fn kill(&mut self, x: i64, y: i64) {
+        if !self.alive(x, y) {
+            return;
+        }
+        self.update((x, y), |state| state & !1);
+
+        for position in Self::neighbor_coordinates(x, y) {
+            self.update(position, |state| state - 0b10);
+        }
+    }
+
+    // Advance the grid by one generation
+    pub fn step(&mut self) {
+        let (births, deaths): (Vec<_>, Vec<_>) = self
+            .cells
+            .iter()
+            .filter_map(|(position, state)| {
+                let alive = state & 1 != 0;
+                let neighbors = (state >> 1) & 0b1111;
+
+                match alive {
+                    true if !self.rule.survives(neighbors) => Some((*position, false)),
+                    false if self.rule.born(neighbors) => Some((*position, true)),
+                    _ => None,
+                }
+            })
+            .partition(|(_, born)| *born);
+
+        for ((x, y), _) in births {
+            self.spawn(x, y);
+        }
+
+        for ((x, y), _) in deaths {
+            self.kill(x, y);
+        }
+    }
+
+    fn update(&mut self, position: (i64, i64), f: impl FnOnce(u8) -> u8) {
+        let state = self.cells.get_mut(&position).unwrap();
+        *state = f(*state);
+
+        if *state == 0 {
+            self.cells.remove(&position);
+        }
+    }
+
+    fn neighbor_coordinates(x: i64, y: i64) -> [(i64, i64); 8] {
+        [
+            (x - 1, y - 1),
+            (x, y - 1),
+            (x + 1, y - 1),
+            (x - 1, y),
+            (x + 1, y),
+            (x - 1, y + 1),
+            (x, y + 1),
+            (x + 1, y + 1),
+        ]
+    }
+}
+
+// Implement Default for InfiniteGrid
+impl Default for InfiniteGrid {
+    fn default() -> Self {
+        InfiniteGrid::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER_OFFSETS: [(i64, i64); 5] = [(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)];
+
+    #[test]
+    fn test_glider() {
+        let mut grid = InfiniteGrid::new();
+
+        for (x, y) in GLIDER_OFFSETS {
+            grid.spawn(x, y);
+        }
+
+        for _ in 0..20 {
+            grid.step();
+            assert_eq!(grid.population(), 5);
+        }
+
+        // A glider travels one cell down and right every 4 generations
+        let mut expected: Vec<(i64, i64)> = GLIDER_OFFSETS
+            .iter()
+            .map(|(x, y)| (x + 20 / 4, y + 20 / 4))
+            .collect();
+        expected.sort_by_key(|(x, y)| (*y, *x));
+
+        assert_eq!(grid.live_cells(), expected);
+    }
+
+    #[test]
+    fn test_spawn_kill() {
+        let mut grid = InfiniteGrid::new();
+        grid.spawn(-3, 7);
+        grid.spawn(-3, 7);
+
+        assert!(grid.alive(-3, 7));
+        assert_eq!(grid.neighbors(-4, 6), 1);
+        assert_eq!(grid.cells.len(), 9);
+
+        grid.kill(-3, 7);
+        assert_eq!(grid.population(), 0);
+        assert!(grid.cells.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::gol::{generator::Generate, grid::Grid};
+
+use std::{cell::RefCell, ops::RangeInclusive};
+
+// Larger than Life: outer totalistic rules over the (2R + 1)^2 box around each cell
+// The count includes the cell itself, so Conway's B3/S23 is radius 1, birth 3..=3, survival 3..=4
+// Counts can exceed the 4-bit neighbor counter of the cells and are taken from the alive bits
+pub struct LtLGenerator<'a, const H: usize, const W: usize> {
+    grid: &'a Grid<H, W>,
+    snapshot: RefCell<Vec<u8>>,
+    radius: usize,
+    birth_range: RangeInclusive<usize>,
+    survive_range: RangeInclusive<usize>,
+}
+
+impl<'a, const H: usize, const W: usize> LtLGenerator<'a, H, W> {
+    pub fn new(
+        grid: &'a Grid<H, W>,
+        radius: usize,
+        birth_range: RangeInclusive<usize>,
+        survive_range: RangeInclusive<usize>,
+    ) -> Self {
+        assert!(radius > 0, "Radius must be at least 1");
+        assert!(
+            2 * radius < H.min(W),
+            "The neighborhood must fit into the grid without overlapping itself"
+        );
+
+        Self {
+            grid,
+            snapshot: RefCell::new(vec![0; H * W]),
+            radius,
+            birth_range,
+            survive_range,
+        }
+    }
+
+    pub fn generate(&self) {
+        let mut snapshot = self.snapshot.borrow_mut();
+        self.grid.as_bytes_into(&mut snapshot);
+
+        let counts = self.box_counts(&snapshot);
+
+        for (i, (state, count)) in snapshot.iter().zip(counts).enumerate() {
+            let alive = state & 1 == 1;
+            let x = (i % W) as isize;
+            let y = (i / W) as isize;
+
+            if alive && !self.survive_range.contains(&count) {
+                self.grid.kill(x, y);
+            } else if !alive && self.birth_range.contains(&count) {
+                self.grid.spawn(x, y);
+            }
+        }
+    }
+
+    // Number of alive cells in the wrapped box around every cell
+    // Sums each row over a sliding horizontal window, then the row sums over a vertical one
+    fn box_counts(&self, snapshot: &[u8]) -> Vec<usize> {
+        let r = self.radius;
+        let mut rows = vec![0; H * W];
+
+        for y in 0..H {
+            let alive = |x: usize| (snapshot[y * W + x % W] & 1) as usize;
+            let mut sum: usize = (W - r..W + r + 1).map(alive).sum();
+
+            for x in 0..W {
+                rows[y * W + x] = sum;
+                sum = sum + alive(x + r + 1) - alive(x + W - r);
+            }
+        }
+
+        let mut counts = vec![0; H * W];
+
+        for x in 0..W {
+            let row = |y: usize| rows[(y % H) * W + x];
+            let mut sum: usize = (H - r..H + r + 1).map(row).sum();
+
+            for y in 0..H {
+                counts[y * W + x] = sum;
+                sum = sum + row(y + r + 1) - row(y + H - r);
+            }
+        }
+
+        counts
+    }
+
+    pub fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+// Implement Generate for LtLGenerator
+impl<'a, const H: usize, const W: usize> Generate<H, W> for LtLGenerator<'a, H, W> {
+    fn step(&self) {
+        self.generate();
+    }
+
+    fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gol::*;
+
+    #[test]
+    fn test_conway_equivalent() {
+        let grid = Grid::<20, 24>::new();
+        randomize_grid_seeded(&grid, 1860, 0.4);
+
+        let reference = grid.clone();
+
+        let generator = LtLGenerator::<20, 24>::new(&grid, 1, 3..=3, 3..=4);
+        let reference_generator = Generator::<20, 24>::new(&reference);
+
+        for _ in 0..10 {
+            generator.generate();
+            reference_generator.generate();
+            assert_eq!(grid.to_bytes(), reference.to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_radius_two() {
+        let grid = Grid::<12, 12>::new();
+        for y in 3..6 {
+            for x in 3..6 {
+                grid.spawn(x, y);
+            }
+        }
+
+        // Every cell of a 3x3 block sees the whole block in its 5x5 box and survives
+        // Dead cells next to an edge of the block see two of its rows or columns
+        let generator = LtLGenerator::<12, 12>::new(&grid, 2, 6..=6, 9..=9);
+        generator.generate();
+
+        assert_eq!(grid.population(), 9 + 4 * 3);
+        assert!(grid.get(4, 4).alive());
+        assert!(grid.get(6, 4).alive());
+        assert!(!grid.get(6, 6).alive());
+        assert!(grid.verify_neighbors());
+    }
+}
@@ -2,15 +2,50 @@
 #![allow(warnings)]
 
 pub mod cell;
+pub mod coord;
+pub mod error;
 pub mod grid;
+pub mod state_grid;
+pub mod dyn_grid;
 pub mod generator;
+pub mod partition;
+pub mod brian_brain;
+pub mod generations;
+pub mod compact;
+pub mod ltl;
+pub mod infinite;
+pub mod rule;
+pub mod simulation;
+pub mod sync;
 pub mod display;
 pub mod utils;
+pub mod ffi;
+pub mod checked;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use cell::Cell;
-pub use grid::Grid;
-pub use generator::Generator;
-pub use display::Display;
-pub use utils::randomize_grid;
+pub use coord::Coord;
+pub use error::GolError;
+pub use grid::{Grid, SymmetryAxis, NEIGHBOR_OFFSETS};
+pub use state_grid::StateGrid;
+pub use dyn_grid::{DynGenerator, DynGrid, DynGridGenerator, MAX_CELLS};
+pub use generator::{Action, Generate, Generator, GridSnapshot, StepStats};
+pub use partition::{partition, worker_range, ChunkStrategy, Region};
+pub use brian_brain::BrianBrainGenerator;
+pub use generations::GenerationsGenerator;
+pub use compact::CompactGenerator;
+pub use ltl::LtLGenerator;
+pub use infinite::InfiniteGrid;
+pub use rule::Rule;
+pub use simulation::{Simulation, SimulationBuilder};
+pub use sync::GenerationSync;
+pub use display::{Display, PanMode, TrailBuffer, Viewport};
+pub use utils::{
+    randomize_grid, randomize_grid_seeded, randomize_grid_with, randomize_population,
+    randomize_region, soup_from_seed, SOUP_SIZE,
+};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGrid;
 
 pub use std::sync::Arc;
\ No newline at end of file
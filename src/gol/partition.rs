@@ -0,0 +1,140 @@
+use std::ops::Range;
+
+// How the board is split between the workers of a parallel generation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkStrategy {
+    // Horizontal bands of whole rows
+    #[default]
+    Rows,
+    // Vertical bands of whole columns
+    Columns,
+    // A grid of rectangular blocks, as close to square as the worker count allows
+    Blocks,
+}
+
+// Rectangle of cells handled by a single worker
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub rows: Range<usize>,
+    pub cols: Range<usize>,
+}
+
+// Rows (or columns) of a board of the given length handled by one worker
+// Worker i gets len * i / workers..len * (i + 1) / workers, so consecutive
+// workers never overlap and the last one always ends at len
+pub fn worker_range(worker: usize, workers: usize, len: usize) -> Range<usize> {
+    assert!(worker < workers, "Worker index out of range");
+    len * worker / workers..len * (worker + 1) / workers
+}
+
+// Split an H x W board into non-overlapping regions covering every cell once
+// Returns at most `workers` regions, empty regions are left out
+pub fn partition<const H: usize, const W: usize>(
+    strategy: ChunkStrategy,
+    workers: usize,
+) -> Vec<Region> {
+    assert!(workers > 0, "Worker count must be at least 1");
+
+    let (row_bands, col_bands) = match strategy {
+        ChunkStrategy::Rows => (workers, 1),
+        ChunkStrategy::Columns => (1, workers),
+        ChunkStrategy::Blocks => {
+            let row_bands = (1..=workers)
+                .take_while(|n| n * n <= workers)
+                .filter(|n| workers % n == 0)
+                .last()
+                .unwrap();
+            (row_bands, workers / row_bands)
+        }
+    };
+
+    (0..row_bands)
+        .flat_map(|i| {
+            (0..col_bands).map(move |j| Region {
+                rows: worker_range(i, row_bands, H),
+                cols: worker_range(j, col_bands, W),
+            })
+        })
+        .filter(|region| !region.rows.is_empty() && !region.cols.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Count how often each cell of an H x W board is covered by the regions
+    fn coverage<const H: usize, const W: usize>(regions: &[Region]) -> Vec<usize> {
+        let mut visits = vec![0; H * W];
+
+        for region in regions {
+            for y in region.rows.clone() {
+                for x in region.cols.clone() {
+                    visits[y * W + x] += 1;
+                }
+            }
+        }
+
+        visits
+    }
+
+    #[test]
+    fn test_worker_range() {
+        assert_eq!(worker_range(0, 4, 100), 0..25);
+        assert_eq!(worker_range(3, 4, 100), 75..100);
+
+        for workers in 1..=10 {
+            let ranges: Vec<Range<usize>> = (0..workers)
+                .map(|worker| worker_range(worker, workers, 37))
+                .collect();
+
+            assert_eq!(ranges[0].start, 0);
+            assert_eq!(ranges[workers - 1].end, 37);
+            assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+        }
+    }
+
+    #[test]
+    fn test_partition_coverage() {
+        for strategy in [
+            ChunkStrategy::Rows,
+            ChunkStrategy::Columns,
+            ChunkStrategy::Blocks,
+        ] {
+            for workers in 1..=12 {
+                let regions = partition::<7, 10>(strategy, workers);
+                assert!(regions.len() <= workers);
+                assert!(coverage::<7, 10>(&regions)
+                    .iter()
+                    .all(|visits| *visits == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_blocks() {
+        let regions = partition::<8, 8>(ChunkStrategy::Blocks, 4);
+
+        assert_eq!(
+            regions,
+            [
+                Region {
+                    rows: 0..4,
+                    cols: 0..4
+                },
+                Region {
+                    rows: 0..4,
+                    cols: 4..8
+                },
+                Region {
+                    rows: 4..8,
+                    cols: 0..4
+                },
+                Region {
+                    rows: 4..8,
+                    cols: 4..8
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Birth/survival rule for outer totalistic automata like Conway's B3/S23
+// Bit n of each mask is set if a cell with n neighbors is born/survives
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+// Implement Rule
+impl Rule {
+    // Create a rule from the neighbor counts that cause a birth or survival
+    pub const fn new(birth: &[u8], survival: &[u8]) -> Self {
+        Self {
+            birth: Self::mask(birth),
+            survival: Self::mask(survival),
+        }
+    }
+
+    // Conway's Game of Life: B3/S23
+    pub const fn conway() -> Self {
+        Self::new(&[3], &[2, 3])
+    }
+
+    #[inline]
+    // Returns true if a dead cell with the given neighbor count is born
+    pub fn born(&self, neighbors: u8) -> bool {
+        self.birth & (1 << neighbors) != 0
+    }
+
+    #[inline]
+    // Returns true if an alive cell with the given neighbor count survives
+    pub fn survives(&self, neighbors: u8) -> bool {
+        self.survival & (1 << neighbors) != 0
+    }
+
+    const fn mask(counts: &[u8]) -> u16 {
+        let mut mask = 0;
+        let mut i = 0;
+
+        while i < counts.len() {
+            assert!(counts[i] <= 8, "Neighbor counts must be between 0 and 8");
+            mask |= 1 << counts[i];
+            i += 1;
+        }
+
+        mask
+    }
+}
+
+// Implement Default for Rule
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::conway()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conway() {
+        let rule = Rule::conway();
+
+        for neighbors in 0..=8 {
+            assert_eq!(rule.born(neighbors), neighbors == 3);
+            assert_eq!(rule.survives(neighbors), neighbors == 2 || neighbors == 3);
+        }
+
+        assert_eq!(rule, Rule::default());
+    }
+
+    #[test]
+    fn test_highlife() {
+        // HighLife: B36/S23
+        let rule = Rule::new(&[3, 6], &[2, 3]);
+
+        assert!(rule.born(3));
+        assert!(rule.born(6));
+        assert!(!rule.born(2));
+        assert!(rule.survives(2));
+        assert!(!rule.survives(6));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::gol::{
+    display::Display, generator::Generator, grid::Grid, rule::Rule, utils::randomize_grid_seeded,
+};
+
+use rand::random;
+
+// Fluent configuration for a grid, its generator and an optional display
+pub struct SimulationBuilder<const H: usize, const W: usize> {
+    seed: Option<u64>,
+    density: f64,
+    rule: Rule,
+    threads: usize,
+    display: bool,
+    delay: u64,
+}
+
+// Implement SimulationBuilder
+impl<const H: usize, const W: usize> SimulationBuilder<H, W> {
+    // Unseeded Conway simulation at 50% density on one thread without a display
+    pub fn new() -> Self {
+        Self {
+            seed: None,
+            density: 0.5,
+            rule: Rule::conway(),
+            threads: 1,
+            display: false,
+            delay: 0,
+        }
+    }
+
+    // Seed for the initial random fill
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // Probability of each cell being alive in the initial random fill
+    pub fn density(mut self, density: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "Density must be between 0 and 1"
+        );
+        self.density = density;
+        self
+    }
+
+    // Birth/survival rule applied each generation
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    // Number of threads generating each generation
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Thread count must be at least 1");
+        self.threads = threads;
+        self
+    }
+
+    // Show the grid in a window after every generation
+    pub fn display(mut self, display: bool) -> Self {
+        self.display = display;
+        self
+    }
+
+    // Delay in milliseconds after each displayed generation
+    pub fn delay(mut self, delay: u64) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    // Create the grid and fill it according to the configuration
+    pub fn build(self) -> Simulation<H, W> {
+        let grid = Grid::new();
+        let seed = self.seed.unwrap_or_else(random);
+        randomize_grid_seeded(&grid, seed, self.density);
+
+        Simulation {
+            grid,
+            rule: self.rule,
+            threads: self.threads,
+            display: self.display,
+            delay: self.delay,
+        }
+    }
+}
+
+// Implement Default for SimulationBuilder
+impl<const H: usize, const W: usize> Default for SimulationBuilder<H, W> {
+    fn default() -> Self {
+        SimulationBuilder::new()
+    }
+}
+
+// A configured grid ready to be run
+pub struct Simulation<const H: usize, const W: usize> {
+    grid: Grid<H, W>,
+    rule: Rule,
+    threads: usize,
+    display: bool,
+    delay: u64,
+}
+
+// Implement Simulation
+impl<const H: usize, const W: usize> Simulation<H, W> {
+    // Run the given number of generations, updating the display if enabled
+    pub fn run(&self, generations: usize) {
+        let generator = Generator::<H, W>::with_rule(&self.grid, self.rule);
+        let mut display = self
+            .display
+            .then(|| Display::<H, W>::new(&self.grid, self.delay));
+
+        for _ in 0..generations {
+            if self.threads > 1 {
+                generator.generate_parallel(self.threads);
+            } else {
+                generator.generate();
+            }
+
+            if let Some(display) = display.as_mut() {
+                display.update();
+            }
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<H, W> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const GOLDEN: [u8; 64] = [
+        8, 4, 2, 0, 2, 3, 8, 7,
+        7, 3, 2, 0, 4, 6, 10, 7,
+        10, 8, 4, 0, 2, 3, 5, 8,
+        5, 5, 4, 0, 2, 4, 4, 4,
+        6, 5, 4, 0, 0, 0, 0, 2,
+        2, 2, 2, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 2, 2, 2,
+        2, 0, 0, 0, 2, 4, 5, 4,
+    ];
+
+    #[test]
+    fn test_headless_golden() {
+        let simulation = SimulationBuilder::<8, 8>::new()
+            .seed(7)
+            .density(0.4)
+            .build();
+
+        simulation.run(10);
+        assert_eq!(simulation.grid().to_bytes(), GOLDEN);
+    }
+
+    #[test]
+    fn test_headless_golden_threaded() {
+        let simulation = SimulationBuilder::<8, 8>::new()
+            .seed(7)
+            .density(0.4)
+            .threads(4)
+            .build();
+
+        simulation.run(10);
+        assert_eq!(simulation.grid().to_bytes(), GOLDEN);
+    }
+}
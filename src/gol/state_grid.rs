@@ -0,0 +1,111 @@
+use std::sync::atomic::Ordering;
+
+use crate::gol::{cell::Cell, grid::Grid};
+
+// Snapshot of the raw cell states of a grid
+// Writes store the byte as is and never touch the neighbor counts of other cells,
+// so it is only meant to hold copies of a grid that keeps the counts itself
+pub struct StateGrid<const H: usize, const W: usize> {
+    cells: Vec<Cell>,
+}
+
+// Implement StateGrid
+impl<const H: usize, const W: usize> StateGrid<H, W> {
+    // Create a new state grid with every byte set to 0
+    pub fn new() -> Self {
+        let mut cells = Vec::with_capacity(H * W);
+
+        for _ in 0..(H * W) {
+            cells.push(Cell::default());
+        }
+
+        Self { cells }
+    }
+
+    // Create a new state grid whose cells use the given load and store orderings
+    pub fn with_orderings(fetch: Ordering, store: Ordering) -> Self {
+        let mut cells = Vec::with_capacity(H * W);
+
+        for _ in 0..(H * W) {
+            cells.push(Cell::new(fetch, store));
+        }
+
+        Self { cells }
+    }
+
+    #[inline]
+    // Translate wrapped 2D coordinates to the index of the cell
+    fn index(x: isize, y: isize) -> usize {
+        let w = W as isize;
+        let h = H as isize;
+
+        let wrapped_x = ((x % w + w) % w) as usize;
+        let wrapped_y = ((y % h + h) % h) as usize;
+
+        wrapped_y * W + wrapped_x
+    }
+
+    #[inline]
+    // Get the cell at the given 2D coordinates
+    pub fn get(&self, x: isize, y: isize) -> &Cell {
+        &self.cells[Self::index(x, y)]
+    }
+
+    #[inline]
+    // Raw state byte of the cell at the given 2D coordinates
+    pub fn fetch(&self, x: isize, y: isize) -> u8 {
+        self.cells[Self::index(x, y)].fetch()
+    }
+
+    #[inline]
+    // Overwrite the raw state byte of a single cell
+    pub fn store(&self, x: isize, y: isize, value: u8) {
+        self.cells[Self::index(x, y)].store(value);
+    }
+
+    #[inline]
+    // Neighbor count stored in the cell at the given 2D coordinates
+    pub fn neighbors(&self, x: isize, y: isize) -> u8 {
+        self.cells[Self::index(x, y)].neighbors()
+    }
+
+    #[inline]
+    // Check if the cell at the given 2D coordinates is alive
+    pub fn alive(&self, x: isize, y: isize) -> bool {
+        self.cells[Self::index(x, y)].alive()
+    }
+
+    #[inline]
+    // Copy the state byte of every cell of the grid
+    // The cells keep their own load and store orderings
+    pub fn copy_from(&self, grid: &Grid<H, W>) {
+        for (cell, other) in self.cells.iter().zip(grid.iter()) {
+            cell.store(other.fetch());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_grid() {
+        let grid = Grid::<6, 8>::new();
+        grid.spawn_shape((2, 2), &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        let state = StateGrid::<6, 8>::new();
+        state.copy_from(&grid);
+
+        for ((x, y), cell) in grid.enumerate_cells() {
+            assert_eq!(state.fetch(x, y), cell.fetch());
+            assert_eq!(state.neighbors(x, y), cell.neighbors());
+            assert_eq!(state.alive(x, y), cell.alive());
+        }
+
+        // Stores leave the surrounding cells alone
+        state.store(6, 4, 0b0000_0001);
+        assert!(state.alive(-2, -2));
+        assert_eq!(state.neighbors(5, 4), 0);
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Barrier,
+};
+
+// Two-phase barrier between a driving thread and a fixed number of worker threads
+// Every generation the driver updates the shared cache and calls next_generation,
+// which releases the workers and returns once all of them called signal_done.
+// Workers loop on wait_for_cache_update until it returns false after finish
+pub struct GenerationSync {
+    start: Barrier,
+    done: Barrier,
+    finished: AtomicBool,
+}
+
+// Implement GenerationSync
+impl GenerationSync {
+    pub fn new(workers: usize) -> Self {
+        assert!(workers > 0, "Worker count must be at least 1");
+
+        Self {
+            start: Barrier::new(workers + 1),
+            done: Barrier::new(workers + 1),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    // Worker side: block until the driver starts the next generation
+    // Returns false once the driver called finish and the worker should exit
+    pub fn wait_for_cache_update(&self) -> bool {
+        self.start.wait();
+        !self.finished.load(Ordering::Acquire)
+    }
+
+    // Worker side: report that this worker's part of the generation is written
+    pub fn signal_done(&self) {
+        self.done.wait();
+    }
+
+    // Driver side: start a generation and wait until every worker signaled done
+    pub fn next_generation(&self) {
+        self.start.wait();
+        self.done.wait();
+    }
+
+    // Driver side: release the waiting workers and tell them to exit
+    pub fn finish(&self) {
+        self.finished.store(true, Ordering::Release);
+        self.start.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{atomic::AtomicUsize, Arc};
+
+    #[test]
+    fn test_generation_sync() {
+        const WORKERS: usize = 4;
+        const GENERATIONS: usize = 5;
+
+        let sync = Arc::new(GenerationSync::new(WORKERS));
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..WORKERS)
+            .map(|_| {
+                let sync = Arc::clone(&sync);
+                let counter = Arc::clone(&counter);
+
+                std::thread::spawn(move || {
+                    while sync.wait_for_cache_update() {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        sync.signal_done();
+                    }
+                })
+            })
+            .collect();
+
+        // Every worker has finished its generation when next_generation returns
+        for generation in 1..=GENERATIONS {
+            sync.next_generation();
+            assert_eq!(counter.load(Ordering::Relaxed), WORKERS * generation);
+        }
+
+        sync.finish();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(counter.load(Ordering::Relaxed), WORKERS * GENERATIONS);
+    }
+}
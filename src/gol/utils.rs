@@ -1,15 +1,961 @@
-use crate::gol::{cell::Cell, grid::Grid};
+use crate::gol::{cell::Cell, dyn_grid::DynGrid, error::GolError, grid::Grid};
 
-use rand::random;
+#[cfg(feature = "image")]
+use crate::gol::generator::Generator;
 
+use rand::{rngs::StdRng, thread_rng, Rng, RngCore, SeedableRng};
+use sha2::{Digest, Sha256};
+
+// Spawn each cell with a probability of one half using the thread RNG
 pub fn randomize_grid<const H: usize, const W: usize>(grid: &Grid<H, W>) {
-    for x in 0..H {
-        for y in 0..W {
-            if random() {
-                let x = x as isize;
-                let y = y as isize;
+    randomize_grid_with(grid, &mut thread_rng(), 0.5);
+}
+
+// Spawn each cell with the given probability using a seeded RNG
+// The same seed and density always produce the same grid
+pub fn randomize_grid_seeded<const H: usize, const W: usize>(
+    grid: &Grid<H, W>,
+    seed: u64,
+    density: f64,
+) {
+    randomize_grid_with(grid, &mut StdRng::seed_from_u64(seed), density);
+}
+
+// Spawn each cell with the given probability drawing from the given RNG
+// Cells are visited in row-major order, one draw per cell
+pub fn randomize_grid_with<const H: usize, const W: usize, R: RngCore>(
+    grid: &Grid<H, W>,
+    rng: &mut R,
+    density: f64,
+) {
+    for y in 0..H {
+        for x in 0..W {
+            if rng.gen_bool(density) {
+                grid.spawn(x as isize, y as isize);
+            }
+        }
+    }
+}
+
+// Spawn each cell inside the rectangle with the given probability using a seeded RNG
+// Both corners are inclusive and cells outside the rectangle are left untouched
+pub fn randomize_region<const H: usize, const W: usize>(
+    grid: &Grid<H, W>,
+    top_left: (isize, isize),
+    bottom_right: (isize, isize),
+    seed: u64,
+    density: f64,
+) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for y in top_left.1..=bottom_right.1 {
+        for x in top_left.0..=bottom_right.0 {
+            if rng.gen_bool(density) {
                 grid.spawn(x, y);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// Side length of the square soups created by soup_from_seed
+pub const SOUP_SIZE: usize = 16;
+
+// Create a grid holding the 16x16 soup apgsearch derives from the seed string,
+// with its top left corner at origin. This is the C1 "hashsoup" convention of
+// apgsearch and Catagolue: the 256 bits of the SHA-256 digest of the seed are the
+// cells, two bytes per row, most significant bit first. A seed taken from a soup
+// search, e.g. "k_" followed by the haul key and the soup index, names the same soup
+pub fn soup_from_seed<const H: usize, const W: usize>(
+    seed: &str,
+    origin: (isize, isize),
+) -> Grid<H, W> {
+    let digest = Sha256::digest(seed.as_bytes());
+    let grid = Grid::new();
+
+    for (j, byte) in digest.iter().enumerate() {
+        for k in 0..8 {
+            if byte & (1 << (7 - k)) != 0 {
+                let x = (k + 8 * (j % 2)) as isize;
+                let y = (j / 2) as isize;
+                grid.spawn(origin.0 + x, origin.1 + y);
+            }
+        }
+    }
+
+    grid
+}
+
+// Create a grid with each shape spawned at its origin
+// Overlapping shapes share cells, a cell covered twice is only spawned once
+pub fn compose<const H: usize, const W: usize>(
+    placements: &[(&[(isize, isize)], (isize, isize))],
+) -> Grid<H, W> {
+    let coords: Vec<(isize, isize)> = placements
+        .iter()
+        .flat_map(|(offsets, (x, y))| offsets.iter().map(move |(dx, dy)| (x + dx, y + dy)))
+        .collect();
+
+    let grid = Grid::new();
+    grid.spawn_batch(&coords);
+    grid
+}
+
+// Spawn exactly `count` distinct cells chosen uniformly at random using a seeded RNG
+// The indices are sampled without replacement with a partial Fisher-Yates shuffle
+pub fn randomize_population<const H: usize, const W: usize>(
+    grid: &Grid<H, W>,
+    count: usize,
+    seed: u64,
+) -> Result<(), GolError> {
+    if count > H * W {
+        return Err(GolError::Population {
+            count,
+            capacity: H * W,
+        });
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut indices: Vec<usize> = (0..H * W).collect();
+
+    for i in 0..count {
+        let j = rng.gen_range(i..indices.len());
+        indices.swap(i, j);
+    }
+
+    for idx in &indices[..count] {
+        grid.spawn((idx % W) as isize, (idx / W) as isize);
+    }
+
+    Ok(())
+}
+
+// Optional extensions to the plain 0/1 CSV format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CsvOptions {
+    // Skip lines starting with '#', e.g. a pattern name
+    pub comments: bool,
+    // The first row is a "width,height" header checked against the data
+    pub dimension_header: bool,
+}
+
+// Read a CSV file of 0/1 values, one row per line, into rows of alive flags
+fn read_csv_rows(path: &str, options: &CsvOptions) -> Result<Vec<Vec<bool>>, GolError> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .filter(|(_, line)| !(options.comments && line.starts_with('#')));
+
+    let header = match options.dimension_header {
+        true => {
+            let (line, header) = lines.next().ok_or(GolError::Csv {
+                line: 1,
+                message: "Missing dimension header".to_string(),
+            })?;
+
+            let dimensions: Vec<usize> = header
+                .split(',')
+                .map(|field| field.trim().parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| GolError::Csv {
+                    line,
+                    message: format!("Invalid dimension header \"{}\"", header),
+                })?;
+
+            if dimensions.len() != 2 {
+                return Err(GolError::Csv {
+                    line,
+                    message: "Dimension header must be \"width,height\"".to_string(),
+                });
+            }
+            Some((dimensions[0], dimensions[1]))
+        }
+        false => None,
+    };
+
+    let mut rows: Vec<Vec<bool>> = Vec::new();
+
+    for (line, text) in lines {
+        let row = text
+            .split(',')
+            .map(|field| match field.trim() {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                other => Err(GolError::Csv {
+                    line,
+                    message: format!("Invalid cell \"{}\"", other),
+                }),
+            })
+            .collect::<Result<Vec<bool>, GolError>>()?;
+
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err(GolError::Csv {
+                    line,
+                    message: format!("Row has {} columns, expected {}", row.len(), first.len()),
+                });
+            }
+        }
+
+        rows.push(row);
+    }
+
+    let w = rows.first().map_or(0, Vec::len);
+
+    if let Some(expected) = header {
+        if expected != (w, rows.len()) {
+            return Err(GolError::DimensionMismatch {
+                expected,
+                actual: (w, rows.len()),
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+// Load a grid from a CSV file of 0/1 values, one row per line
+// The file must have exactly H rows of W columns
+pub fn create_grid_from_file<const H: usize, const W: usize>(
+    path: &str,
+) -> Result<Grid<H, W>, GolError> {
+    create_grid_from_file_with_options(path, &CsvOptions::default())
+}
+
+// Load a grid from a CSV file, skipping comments and headers as configured
+pub fn create_grid_from_file_with_options<const H: usize, const W: usize>(
+    path: &str,
+    options: &CsvOptions,
+) -> Result<Grid<H, W>, GolError> {
+    let rows = read_csv_rows(path, options)?;
+
+    let w = rows.first().map_or(0, Vec::len);
+    if (w, rows.len()) != (W, H) {
+        return Err(GolError::DimensionMismatch {
+            expected: (W, H),
+            actual: (w, rows.len()),
+        });
+    }
+
+    let grid = Grid::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, alive) in row.iter().enumerate() {
+            if *alive {
+                grid.spawn(x as isize, y as isize);
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+// Load a grid from a CSV file of 0/1 values, one row per line
+// The dimensions are inferred from the number of rows and columns
+pub fn create_dyn_grid_from_file(path: &str) -> Result<DynGrid, GolError> {
+    create_dyn_grid_from_file_with_options(path, &CsvOptions::default())
+}
+
+// Load a runtime sized grid from a CSV file, skipping comments and headers as configured
+pub fn create_dyn_grid_from_file_with_options(
+    path: &str,
+    options: &CsvOptions,
+) -> Result<DynGrid, GolError> {
+    let rows = read_csv_rows(path, options)?;
+
+    let h = rows.len();
+    let w = rows.first().map_or(0, Vec::len);
+
+    if w == 0 || h == 0 {
+        return Err(GolError::Csv {
+            line: 1,
+            message: "File contains no cells".to_string(),
+        });
+    }
+
+    let grid = DynGrid::new(w, h);
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, alive) in row.iter().enumerate() {
+            if *alive {
+                grid.spawn(x as isize, y as isize);
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
+// Load a run length encoded pattern with its top left corner at origin
+// Lines starting with '#' are skipped, the "x = .., y = .." header is required
+// and the rule is ignored. The origin and every alive cell must lie inside the grid
+// and the header may not be larger than the grid
+pub fn create_grid_from_rle<const H: usize, const W: usize>(
+    path: &str,
+    origin: (isize, isize),
+) -> Result<Grid<H, W>, GolError> {
+    let contents = std::fs::read_to_string(path)?;
+
+    let grid = Grid::new();
+    if grid.get_checked(origin.0, origin.1).is_none() {
+        return Err(GolError::OutOfBounds {
+            x: origin.0,
+            y: origin.1,
+        });
+    }
+
+    let mut lines = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header = lines
+        .next()
+        .ok_or_else(|| GolError::Rle("Missing header".to_string()))?;
+
+    let dimension = |name: &str| {
+        header
+            .split(',')
+            .filter_map(|field| field.split_once('='))
+            .find(|(key, _)| key.trim() == name)
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .ok_or_else(|| GolError::Rle(format!("Invalid header \"{}\"", header)))
+    };
+    let (w, h) = (dimension("x")?, dimension("y")?);
+
+    if w > W || h > H {
+        return Err(GolError::DimensionMismatch {
+            expected: (W, H),
+            actual: (w, h),
+        });
+    }
+
+    let exceeds = || GolError::Rle(format!("Pattern exceeds the {}x{} header", w, h));
+
+    let (mut x, mut y) = (0usize, 0usize);
+    let mut count: Option<usize> = None;
+
+    for tag in lines.flat_map(str::chars) {
+        if let Some(digit) = tag.to_digit(10) {
+            count = count
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|count| count.checked_add(digit as usize));
+
+            if count.is_none() {
+                return Err(GolError::Rle("Run count is too large".to_string()));
+            }
+            continue;
+        }
+
+        let run = count.take().unwrap_or(1);
+
+        match tag {
+            'b' => x = x.checked_add(run).ok_or_else(exceeds)?,
+            'o' => {
+                let end = x
+                    .checked_add(run)
+                    .filter(|end| *end <= w && y < h)
+                    .ok_or_else(exceeds)?;
+
+                for column in x..end {
+                    // The origin is inside the grid and the header no larger than it,
+                    // so the sums stay far from overflowing
+                    let (cx, cy) = (origin.0 + column as isize, origin.1 + y as isize);
+
+                    if grid.get_checked(cx, cy).is_none() {
+                        return Err(GolError::OutOfBounds { x: cx, y: cy });
+                    }
+                    grid.spawn(cx, cy);
+                }
+                x = end;
+            }
+            '$' => {
+                x = 0;
+                y = y.checked_add(run).ok_or_else(exceeds)?;
+            }
+            '!' => return Ok(grid),
+            other => return Err(GolError::Rle(format!("Invalid tag '{}'", other))),
+        }
+    }
+
+    Err(GolError::Rle("Missing terminating '!'".to_string()))
+}
+
+// Save a grid as H rows of W comma separated 0/1 values
+// The output can be loaded back with create_grid_from_file
+pub fn save_grid_to_csv<const H: usize, const W: usize>(
+    grid: &Grid<H, W>,
+    path: &str,
+) -> Result<(), GolError> {
+    let mut contents = String::with_capacity(H * W * 2);
+
+    for y in 0..H {
+        let row: Vec<&str> = (0..W)
+            .map(|x| match grid.get(x as isize, y as isize).alive() {
+                true => "1",
+                false => "0",
+            })
+            .collect();
+
+        contents.push_str(&row.join(","));
+        contents.push('\n');
+    }
+
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+// Load a grid from an image, spawning a cell wherever the grayscale
+// pixel value exceeds the threshold. The image must be W x H pixels
+#[cfg(feature = "image")]
+pub fn load_from_image<const H: usize, const W: usize>(
+    path: &str,
+    threshold: u8,
+) -> Result<Grid<H, W>, GolError> {
+    let image = image::open(path)?.into_luma8();
+
+    let (w, h) = image.dimensions();
+    if (w as usize, h as usize) != (W, H) {
+        return Err(GolError::DimensionMismatch {
+            expected: (W, H),
+            actual: (w as usize, h as usize),
+        });
+    }
+
+    let grid = Grid::new();
+
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel.0[0] > threshold {
+            grid.spawn(x as isize, y as isize);
+        }
+    }
+
+    Ok(grid)
+}
+
+// Save the grid as a grayscale PNG with white alive and black dead cells
+// Every cell is drawn as a scale x scale square of pixels
+#[cfg(feature = "image")]
+pub fn save_png<const H: usize, const W: usize>(
+    grid: &Grid<H, W>,
+    path: &str,
+    scale: usize,
+) -> Result<(), GolError> {
+    assert!(scale > 0, "Scale must be at least 1");
+
+    let image = image::GrayImage::from_fn((W * scale) as u32, (H * scale) as u32, |x, y| {
+        let cell = grid.get(x as isize / scale as isize, y as isize / scale as isize);
+        image::Luma([if cell.alive() { 255 } else { 0 }])
+    });
+
+    image.save(path)?;
+    Ok(())
+}
+
+// Save `frames` PNGs named frame_0000.png, frame_0001.png, ... into dir,
+// generating once after each frame. The directory is created if it is missing
+#[cfg(feature = "image")]
+pub fn export_flipbook<const H: usize, const W: usize>(
+    generator: &mut Generator<H, W>,
+    frames: usize,
+    dir: &str,
+    scale: usize,
+) -> Result<(), GolError> {
+    std::fs::create_dir_all(dir)?;
+
+    for frame in 0..frames {
+        let path = std::path::Path::new(dir).join(format!("frame_{:04}.png", frame));
+        save_png(generator.grid(), path.to_str().unwrap(), scale)?;
+        generator.generate();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_randomize_grid_seeded() {
+        let grid = Grid::<16, 16>::new();
+        let other = Grid::<16, 16>::new();
+
+        randomize_grid_seeded(&grid, 42, 0.3);
+        randomize_grid_seeded(&other, 42, 0.3);
+
+        assert!(grid == other);
+
+        let empty = Grid::<16, 16>::new();
+        randomize_grid_seeded(&empty, 42, 0.0);
+        assert_eq!(empty.population(), 0);
+
+        let full = Grid::<16, 16>::new();
+        randomize_grid_seeded(&full, 42, 1.0);
+        assert_eq!(full.population(), 16 * 16);
+    }
+
+    #[test]
+    fn test_randomize_grid_with() {
+        use rand::rngs::mock::StepRng;
+
+        // Draws cycle through 0, 1/4, 2/4 and 3/4 of the u64 range,
+        // so with a density of one half every other pair of cells is spawned
+        let grid = Grid::<4, 4>::new();
+        randomize_grid_with(&grid, &mut StepRng::new(0, 1 << 62), 0.5);
+
+        assert_eq!(grid.render_ascii('#', '.'), "##..\n##..\n##..\n##..\n");
+        assert!(grid.verify_neighbors());
+    }
+
+    #[test]
+    fn test_randomize_region() {
+        let grid = Grid::<64, 64>::new();
+        randomize_region(&grid, (16, 16), (47, 47), 1852, 0.3);
+
+        let inside = |x: isize, y: isize| (16..=47).contains(&x) && (16..=47).contains(&y);
+
+        for ((x, y), cell) in grid.enumerate_cells() {
+            if !inside(x, y) {
+                assert!(
+                    !cell.alive(),
+                    "Cell ({}, {}) outside the region is alive",
+                    x,
+                    y
+                );
+            }
+        }
+
+        let density = grid.population() as f64 / (32 * 32) as f64;
+        assert!(
+            (density - 0.3).abs() < 0.05,
+            "Region density is {}",
+            density
+        );
+    }
+
+    #[test]
+    fn test_soup_from_seed() {
+        // SHA-256 of "abc" is ba7816bf 8f01cfea 414140de 5dae2223 b00361a3 96177a9c b410ff61 f20015ad
+        const ROWS: [u16; SOUP_SIZE] = [
+            0xba78, 0x16bf, 0x8f01, 0xcfea, 0x4141, 0x40de, 0x5dae, 0x2223, 0xb003, 0x61a3, 0x9617,
+            0x7a9c, 0xb410, 0xff61, 0xf200, 0x15ad,
+        ];
+
+        let soup = soup_from_seed::<32, 32>("abc", (8, 4));
+        assert!(soup.verify_neighbors());
+        assert_eq!(soup.population(), 120);
+
+        for (y, row) in ROWS.iter().enumerate() {
+            for x in 0..SOUP_SIZE {
+                let alive = row & (1 << (15 - x)) != 0;
+                assert_eq!(soup.get(8 + x as isize, 4 + y as isize).alive(), alive);
+            }
+        }
+
+        // Nothing is spawned outside the 16x16 square
+        assert!(soup
+            .enumerate_cells()
+            .filter(|(_, cell)| cell.alive())
+            .all(|((x, y), _)| (8..24).contains(&x) && (4..20).contains(&y)));
+
+        // The same soup moved to another origin
+        let moved = soup_from_seed::<32, 32>("abc", (0, 0));
+        assert_eq!(soup.equals_translated(&moved), Some((8, 4)));
+
+        let other = soup_from_seed::<32, 32>("k_gol1880", (8, 4));
+        assert!(other == soup_from_seed::<32, 32>("k_gol1880", (8, 4)));
+        assert_eq!(other.population(), 114);
+        assert_ne!(other.to_bytes(), soup.to_bytes());
+    }
+
+    #[test]
+    fn test_compose() {
+        use crate::gol::generator::Generator;
+
+        // Gliders heading down-right and up-left
+        let south_east: [(isize, isize); 5] = [(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)];
+        let north_west: [(isize, isize); 5] = [(0, 2), (0, 1), (0, 0), (1, 0), (2, 1)];
+
+        let grid = compose::<20, 20>(&[(&south_east, (2, 2)), (&north_west, (12, 12))]);
+        assert_eq!(grid.population(), 10);
+        assert!(grid.verify_neighbors());
+
+        let generator = Generator::<20, 20>::new(&grid);
+        let collision = (1..=40).find(|_| {
+            generator.generate();
+            grid.population() != 10
+        });
+
+        assert!(collision.is_some(), "The gliders never collided");
+
+        // Overlapping shapes do not double count neighbors
+        let block: [(isize, isize); 4] = [(0, 0), (1, 0), (0, 1), (1, 1)];
+        let overlapping = compose::<8, 8>(&[(&block, (2, 2)), (&block, (3, 2))]);
+        assert_eq!(overlapping.population(), 6);
+        assert!(overlapping.verify_neighbors());
+    }
+
+    #[test]
+    fn test_randomize_population() {
+        let grid = Grid::<8, 12>::new();
+        randomize_population(&grid, 30, 7).unwrap();
+        assert_eq!(grid.population(), 30);
+        assert!(grid.verify_neighbors());
+
+        let other = Grid::<8, 12>::new();
+        randomize_population(&other, 30, 7).unwrap();
+        assert!(grid == other);
+
+        let full = Grid::<8, 12>::new();
+        randomize_population(&full, 96, 7).unwrap();
+        assert_eq!(full.population(), 96);
+
+        let error = randomize_population(&Grid::<8, 12>::new(), 97, 7);
+        assert!(matches!(
+            error,
+            Err(GolError::Population {
+                count: 97,
+                capacity: 96
+            })
+        ));
+    }
+
+    #[test]
+    fn test_save_grid_to_csv() {
+        let path = std::env::temp_dir().join("gol_test_save_grid_to_csv.csv");
+        let path = path.to_str().unwrap();
+
+        let grid = Grid::<5, 7>::new();
+        grid.spawn_shape((1, 1), &[(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)]);
+
+        save_grid_to_csv(&grid, path).unwrap();
+        let contents = std::fs::read_to_string(path).unwrap();
+        let loaded = create_grid_from_file::<5, 7>(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(contents.lines().count(), 5);
+        assert_eq!(contents.lines().next().unwrap(), "0,0,0,0,0,0,0");
+        assert_eq!(contents.lines().nth(1).unwrap(), "0,0,0,1,0,0,0");
+        assert!(loaded == grid);
+    }
+
+    fn load_block(name: &str, contents: &str, options: &CsvOptions) -> Grid<4, 4> {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).unwrap();
+
+        let grid = create_grid_from_file_with_options::<4, 4>(path.to_str().unwrap(), options);
+        std::fs::remove_file(&path).unwrap();
+
+        grid.unwrap()
+    }
+
+    #[test]
+    fn test_csv_comments() {
+        let options = CsvOptions {
+            comments: true,
+            ..CsvOptions::default()
+        };
+
+        let grid = load_block(
+            "gol_test_csv_comments.csv",
+            "# Block\n0,0,0,0\n# Still life\n0,1,1,0\n0,1,1,0\n0,0,0,0\n",
+            &options,
+        );
+
+        let expected = Grid::<4, 4>::new();
+        expected.spawn_shape((1, 1), &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(grid == expected);
+    }
+
+    #[test]
+    fn test_csv_dimension_header() {
+        let options = CsvOptions {
+            comments: true,
+            dimension_header: true,
+        };
+
+        let grid = load_block(
+            "gol_test_csv_dimension_header.csv",
+            "# Block\n4,4\n0,0,0,0\n0,1,1,0\n0,1,1,0\n0,0,0,0\n",
+            &options,
+        );
+
+        let expected = Grid::<4, 4>::new();
+        expected.spawn_shape((1, 1), &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(grid == expected);
+    }
+
+    fn load_error(name: &str, contents: &str, options: &CsvOptions) -> GolError {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).unwrap();
+
+        let result = create_grid_from_file_with_options::<4, 4>(path.to_str().unwrap(), options);
+        std::fs::remove_file(&path).unwrap();
+
+        result.err().unwrap()
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let options = CsvOptions {
+            comments: true,
+            dimension_header: true,
+        };
+
+        let missing = create_grid_from_file::<4, 4>("gol_test_does_not_exist.csv");
+        assert!(matches!(missing, Err(GolError::Io(_))));
+
+        let error = load_error(
+            "gol_test_csv_invalid_cell.csv",
+            "0,0,0,0\n0,1,x,0\n",
+            &CsvOptions::default(),
+        );
+        assert!(matches!(error, GolError::Csv { line: 2, .. }));
+
+        let error = load_error(
+            "gol_test_csv_ragged.csv",
+            "# Block\n0,0,0,0\n0,1,1\n",
+            &CsvOptions {
+                comments: true,
+                ..CsvOptions::default()
+            },
+        );
+        assert!(matches!(error, GolError::Csv { line: 3, .. }));
+
+        let error = load_error("gol_test_csv_bad_header.csv", "4,4,4\n0,0,0,0\n", &options);
+        assert!(matches!(error, GolError::Csv { line: 1, .. }));
+
+        let error = load_error(
+            "gol_test_csv_header_mismatch.csv",
+            "4,4\n0,0,0,0\n0,1,1,0\n",
+            &options,
+        );
+        assert!(matches!(
+            error,
+            GolError::DimensionMismatch {
+                expected: (4, 4),
+                actual: (4, 2)
+            }
+        ));
+
+        let error = load_error(
+            "gol_test_csv_wrong_size.csv",
+            "0,0,0\n0,1,1\n0,1,1\n",
+            &CsvOptions::default(),
+        );
+        assert!(matches!(
+            error,
+            GolError::DimensionMismatch {
+                expected: (4, 4),
+                actual: (3, 3)
+            }
+        ));
+    }
+
+    fn load_rle(
+        name: &str,
+        contents: &str,
+        origin: (isize, isize),
+    ) -> Result<Grid<8, 8>, GolError> {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, contents).unwrap();
+
+        let grid = create_grid_from_rle::<8, 8>(path.to_str().unwrap(), origin);
+        std::fs::remove_file(&path).unwrap();
+
+        grid
+    }
+
+    #[test]
+    fn test_create_grid_from_rle() {
+        let grid = load_rle(
+            "gol_test_rle_glider.rle",
+            "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$\n3o!\n",
+            (2, 1),
+        )
+        .unwrap();
+
+        let expected = Grid::<8, 8>::new();
+        expected.spawn_shape((2, 1), &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert!(grid == expected);
+
+        // Multi digit runs and blank lines
+        let grid = load_rle("gol_test_rle_runs.rle", "x = 8, y = 3\n8o2$\n8o!", (0, 0)).unwrap();
+        assert_eq!(grid.population(), 16);
+        assert!(grid.get(7, 2).alive());
+        assert!(!grid.get(0, 1).alive());
+    }
+
+    #[test]
+    fn test_rle_errors() {
+        let missing = create_grid_from_rle::<8, 8>("gol_test_does_not_exist.rle", (0, 0));
+        assert!(matches!(missing, Err(GolError::Io(_))));
+
+        let error = load_rle("gol_test_rle_empty.rle", "#N Empty\n", (0, 0));
+        assert!(matches!(error, Err(GolError::Rle(message)) if message == "Missing header"));
+
+        let error = load_rle("gol_test_rle_bad_header.rle", "x = 3\n3o!", (0, 0));
+        assert!(matches!(error, Err(GolError::Rle(_))));
+
+        let error = load_rle("gol_test_rle_bad_tag.rle", "x = 3, y = 1\n2oz!", (0, 0));
+        assert!(matches!(error, Err(GolError::Rle(message)) if message == "Invalid tag 'z'"));
+
+        let error = load_rle("gol_test_rle_too_wide.rle", "x = 2, y = 1\n3o!", (0, 0));
+        assert!(matches!(error, Err(GolError::Rle(_))));
+
+        let error = load_rle("gol_test_rle_unterminated.rle", "x = 3, y = 1\n3o", (0, 0));
+        assert!(matches!(error, Err(GolError::Rle(_))));
+
+        let error = load_rle("gol_test_rle_outside.rle", "x = 3, y = 1\n3o!", (6, 2));
+        assert!(matches!(error, Err(GolError::OutOfBounds { x: 8, y: 2 })));
+
+        let error = load_rle("gol_test_rle_bad_origin.rle", "x = 1, y = 1\no!", (-1, 0));
+        assert!(matches!(error, Err(GolError::OutOfBounds { x: -1, y: 0 })));
+
+        let error = load_rle("gol_test_rle_large_header.rle", "x = 9, y = 1\no!", (0, 0));
+        assert!(matches!(
+            error,
+            Err(GolError::DimensionMismatch {
+                expected: (8, 8),
+                actual: (9, 1)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rle_overflow() {
+        // Run counts that do not fit a usize
+        let error = load_rle(
+            "gol_test_rle_long_count.rle",
+            "x = 3, y = 1\n99999999999999999999999o!",
+            (0, 0),
+        );
+        assert!(
+            matches!(error, Err(GolError::Rle(message)) if message == "Run count is too large")
+        );
+
+        // Runs that overflow the column or row when added
+        let max = usize::MAX;
+        for (name, body) in [
+            ("gol_test_rle_dead_run.rle", format!("2b{}bo!", max)),
+            ("gol_test_rle_alive_run.rle", format!("o{}o!", max)),
+            ("gol_test_rle_row_run.rle", format!("$${}$o!", max)),
+        ] {
+            let error = load_rle(name, &format!("x = 3, y = 3\n{}", body), (0, 0));
+            assert!(matches!(error, Err(GolError::Rle(_))), "{}", name);
+        }
+
+        // A huge run that fits a usize fails before spawning anything
+        let error = load_rle(
+            "gol_test_rle_huge_run.rle",
+            &format!("x = 3, y = 1\n{}o!", max),
+            (0, 0),
+        );
+        assert!(matches!(error, Err(GolError::Rle(_))));
+    }
+
+    #[test]
+    fn test_create_dyn_grid_from_file() {
+        let path = std::env::temp_dir().join("gol_test_create_dyn_grid_from_file.csv");
+        std::fs::write(&path, "0,0,0,0,0\n0,1,1,0,0\n0,1,1,0,0\n").unwrap();
+
+        let grid = create_dyn_grid_from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(grid.width(), 5);
+        assert_eq!(grid.height(), 3);
+
+        for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            let cell = grid.get(x, y);
+            assert!(cell.alive());
+            assert_eq!(cell.neighbors(), 3);
+        }
+
+        assert!(!grid.get(0, 0).alive());
+        assert!(!grid.get(4, 2).alive());
+    }
+
+    // 4x3 grayscale PNG with the rows
+    // 0 255 0 0 / 0 255 0 200 / 0 255 0 50
+    #[cfg(feature = "image")]
+    #[rustfmt::skip]
+    const TINY_PNG: [u8; 76] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x03, 0x08, 0x00, 0x00, 0x00, 0x00, 0x91, 0x9f, 0xf1,
+        0x1a, 0x00, 0x00, 0x00, 0x13, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x60, 0xf8, 0xcf, 0x00,
+        0x04, 0xff, 0x19, 0x4e, 0x80, 0x08, 0x23, 0x00, 0x1c, 0xd9, 0x03, 0xf8, 0xec, 0xdc, 0xdb, 0xcb,
+        0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_load_from_image() {
+        let path = std::env::temp_dir().join("gol_test_load_from_image.png");
+        std::fs::write(&path, TINY_PNG).unwrap();
+        let path = path.to_str().unwrap();
+
+        let grid = load_from_image::<3, 4>(path, 100).unwrap();
+        let wrong_size = load_from_image::<4, 4>(path, 100);
+        std::fs::remove_file(path).unwrap();
+
+        let expected = Grid::<3, 4>::new();
+        expected.spawn_shape((0, 0), &[(1, 0), (1, 1), (1, 2), (3, 1)]);
+
+        assert!(grid == expected);
+        assert!(matches!(
+            wrong_size,
+            Err(GolError::DimensionMismatch {
+                expected: (4, 4),
+                actual: (4, 3)
+            })
+        ));
+
+        let missing = load_from_image::<3, 4>("gol_test_does_not_exist.png", 100);
+        assert!(matches!(missing, Err(GolError::Io(_))));
+
+        std::fs::write(path, b"not a png").unwrap();
+        let invalid = load_from_image::<3, 4>(path, 100);
+        std::fs::remove_file(path).unwrap();
+        assert!(matches!(invalid, Err(GolError::Image(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_export_flipbook() {
+        let dir = std::env::temp_dir().join("gol_test_export_flipbook");
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.to_str().unwrap();
+
+        let grid = Grid::<5, 5>::new();
+        grid.spawn_batch(&[(1, 2), (2, 2), (3, 2)]);
+
+        let mut generator = Generator::<5, 5>::new(&grid);
+        export_flipbook(&mut generator, 3, dir, 2).unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            ["frame_0000.png", "frame_0001.png", "frame_0002.png"]
+        );
+        assert_eq!(generator.generation(), 3);
+
+        // The second frame shows the vertical phase of the blinker at twice the size
+        let frame = image::open(format!("{}/frame_0001.png", dir))
+            .unwrap()
+            .into_luma8();
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(frame.dimensions(), (10, 10));
+        assert_eq!(frame.get_pixel(5, 3).0, [255]);
+        assert_eq!(frame.get_pixel(3, 5).0, [0]);
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::gol::{generator::Generator, grid::Grid};
+use wasm_bindgen::prelude::*;
+
+const H: usize = 64;
+const W: usize = 64;
+
+// Browser facing wrapper around a fixed size grid
+// A Cell keeps its load and store orderings next to the state byte, so the states
+// are not contiguous in memory and have to be mirrored into a byte buffer for JS.
+// The mirror is only rebuilt when cells_ptr is called after the grid changed
+#[wasm_bindgen]
+pub struct WasmGrid {
+    grid: Arc<Grid<H, W>>,
+    generator: Generator<'static, H, W>,
+    bytes: Vec<u8>,
+    dirty: bool,
+}
+
+// Implement WasmGrid
+#[wasm_bindgen]
+impl WasmGrid {
+    // Create a new grid with dead cells and 0 neighbors
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        let grid = Arc::new(Grid::new());
+
+        Self {
+            generator: Generator::new_shared(Arc::clone(&grid)),
+            grid,
+            bytes: vec![0; H * W],
+            dirty: false,
+        }
+    }
+
+    // Spawn a cell at the given 2D coordinates
+    pub fn spawn(&mut self, x: isize, y: isize) {
+        self.grid.spawn(x, y);
+        self.dirty = true;
+    }
+
+    // Advance the grid by one generation
+    pub fn step(&mut self) {
+        self.generator.generate();
+        self.dirty = true;
+    }
+
+    // Pointer to the cell state bytes in row-major order
+    // The pointer stays valid until the next call to spawn or step
+    pub fn cells_ptr(&mut self) -> *const u8 {
+        if self.dirty {
+            self.grid.as_bytes_into(&mut self.bytes);
+            self.dirty = false;
+        }
+
+        self.bytes.as_ptr()
+    }
+
+    // Number of cell state bytes behind cells_ptr
+    pub fn cells_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+// Implement Default for WasmGrid
+impl Default for WasmGrid {
+    fn default() -> Self {
+        WasmGrid::new()
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    pub const BLINKER_OFFSETS: [(isize, isize); 3] = [(0, 1), (1, 1), (2, 1)];
+
+    #[wasm_bindgen_test]
+    fn test_step_blinker() {
+        let mut grid = WasmGrid::new();
+
+        for (x, y) in BLINKER_OFFSETS {
+            grid.spawn(x + 10, y + 10);
+        }
+
+        let read = |grid: &mut WasmGrid| unsafe {
+            std::slice::from_raw_parts(grid.cells_ptr(), grid.cells_len()).to_vec()
+        };
+
+        let before = read(&mut grid);
+        assert_eq!(before.len(), H * W);
+
+        grid.step();
+        let after = read(&mut grid);
+
+        assert_ne!(before, after);
+
+        // The horizontal blinker turns vertical around its center
+        assert_eq!(after[10 * W + 11] & 1, 1);
+        assert_eq!(after[11 * W + 11] & 1, 1);
+        assert_eq!(after[12 * W + 11] & 1, 1);
+        assert_eq!(after[11 * W + 10] & 1, 0);
+        assert_eq!(after[11 * W + 12] & 1, 0);
+    }
+}
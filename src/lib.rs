@@ -0,0 +1,3 @@
+pub mod gol;
+
+pub use gol::*;
@@ -1,6 +1,4 @@
-mod gol;
-
-use gol::*;
+use gol_atomic::*;
 
 const H: usize = 100;
 const W: usize = 100;
@@ -11,33 +9,13 @@ const BENCHMARKS: usize = 10;
 
 // Single threaded
 pub fn single_threaded() {
-    let grid: Grid<H, W> = Grid::<H, W>::new();
-    let grid = Arc::new(&grid);
-
-    randomize_grid(&grid);
-
-    let generator = Generator::<H, W>::new(Arc::clone(&grid));
-    let mut display = None;
-
-    if DISPLAY {
-        let grid_ref = Arc::new(generator.grid());
-        display = Some(Display::<H, W>::new(grid_ref, DISPLAY_DELAY));
-    }
+    let simulation = SimulationBuilder::<H, W>::new()
+        .display(DISPLAY)
+        .delay(DISPLAY_DELAY)
+        .build();
 
     let start = std::time::Instant::now();
-    match display {
-        Some(ref mut display) => {
-            for _ in 0..GENERATIONS {
-                generator.generate();
-                display.update();
-            }
-        }
-        None => {
-            for _ in 0..GENERATIONS {
-                generator.generate();
-            }
-        }
-    }
+    simulation.run(GENERATIONS);
     let end = std::time::Instant::now();
     println!(
         "Time taken to generate {} generations of size {} {}: {:?}",
@@ -0,0 +1,36 @@
+use gol_atomic::*;
+
+const H: usize = 16;
+const W: usize = 16;
+
+pub const GLIDER_OFFSETS: [(isize, isize); 5] = [(2, 0), (2, 1), (2, 2), (1, 2), (0, 1)];
+
+#[test]
+fn test_single_threaded_glider() {
+    let grid: Grid<H, W> = Grid::<H, W>::new();
+    grid.spawn_shape((0, 0), &GLIDER_OFFSETS);
+
+    let generator = Generator::<H, W>::new(&grid);
+
+    for _ in 0..8 {
+        generator.generate();
+    }
+
+    // Two periods move the glider two cells down and right
+    let expected = Grid::<H, W>::new();
+    expected.spawn_shape((2, 2), &GLIDER_OFFSETS);
+
+    assert!(grid == expected);
+    assert_eq!(generator.generation(), 8);
+}
+
+#[test]
+fn test_single_threaded_simulation() {
+    let simulation = SimulationBuilder::<H, W>::new().seed(1).build();
+    let population = simulation.grid().population();
+
+    simulation.run(5);
+
+    assert!(simulation.grid().verify_neighbors());
+    assert_ne!(simulation.grid().population(), population);
+}